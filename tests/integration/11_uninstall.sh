@@ -0,0 +1,99 @@
+#!/usr/bin/env sh
+# Test: Uninstall command
+#
+# This test verifies that `samoyed uninstall` reverses `samoyed init` without
+# destroying the hooks users wrote themselves, and that it refuses to touch a
+# core.hooksPath value that belongs to another tool.
+#
+# Tests:
+# 1. Uninstall removes the _ directory and unsets core.hooksPath
+# 2. User hooks in .samoyed/ are preserved
+# 3. Running uninstall twice is a no-op
+# 4. --purge removes the whole samoyed directory
+# 5. A foreign core.hooksPath makes uninstall refuse, unless --force is given
+
+# Load test helper functions regardless of current working directory
+integration_script_dir="$(cd "$(dirname "$0")" && pwd)"
+integration_repo_root="$(cd "$integration_script_dir/../.." && pwd)"
+cd "$integration_repo_root"
+. "$integration_repo_root/tests/integration/functions.sh"
+unset integration_script_dir
+unset integration_repo_root
+
+parse_common_args "$@"
+
+# Build Samoyed binary if needed
+build_samoyed
+
+# Set up isolated test environment
+setup
+
+# Test: Uninstall after a default init
+echo "Testing: samoyed uninstall after init"
+init_samoyed
+create_hook "pre-commit" "echo 'user hook' && exit 1"
+
+expect 0 "$SAMOYED_BIN uninstall"
+ok "samoyed uninstall completed successfully"
+
+if [ -d ".samoyed/_" ]; then
+    error "Expected .samoyed/_ to be removed"
+fi
+expect_file_exists ".samoyed/pre-commit"
+ok "Wrapper directory removed and user hook preserved"
+
+if git config core.hooksPath >/dev/null 2>&1; then
+    error "Expected core.hooksPath to be unset"
+fi
+ok "core.hooksPath unset"
+
+# The preserved hook must no longer run
+echo "uninstalled" >>test.txt
+git add test.txt
+expect 0 "git commit -m 'Commit after uninstall' --quiet"
+ok "Hooks no longer run after uninstall"
+
+# Test: Uninstall is idempotent
+echo "Testing: samoyed uninstall twice"
+output=$("$SAMOYED_BIN" uninstall 2>&1)
+if echo "$output" | grep -q "Nothing to uninstall"; then
+    ok "Second uninstall is a no-op"
+else
+    error "Expected 'Nothing to uninstall', got: $output"
+fi
+
+# Test: --purge removes user hooks too
+echo "Testing: samoyed uninstall --purge"
+init_samoyed
+expect 0 "$SAMOYED_BIN uninstall --purge"
+if [ -d ".samoyed" ]; then
+    error "Expected .samoyed to be removed by --purge"
+fi
+ok "--purge removed the samoyed directory"
+
+# Test: Foreign core.hooksPath is left untouched
+echo "Testing: samoyed uninstall with a foreign core.hooksPath"
+init_samoyed
+git config core.hooksPath .husky/_
+
+expect 1 "$SAMOYED_BIN uninstall --purge"
+expect_dir_exists ".samoyed/_"
+expect_hooks_path_to_be ".husky/_"
+ok "Uninstall refused and removed nothing"
+
+output=$("$SAMOYED_BIN" uninstall --force 2>&1)
+if echo "$output" | grep -q "leaving it unchanged"; then
+    ok "Uninstall --force warned about the foreign core.hooksPath"
+else
+    error "Expected a warning about core.hooksPath, got: $output"
+fi
+if [ -d ".samoyed/_" ]; then
+    error "Expected uninstall --force to remove .samoyed/_"
+fi
+expect_hooks_path_to_be ".husky/_"
+ok "Foreign core.hooksPath left unchanged"
+
+echo
+echo "========================================"
+echo "✅ ALL TESTS PASSED"
+echo "========================================"
@@ -0,0 +1,70 @@
+#!/usr/bin/env sh
+# Test: Dry-run initialization
+#
+# This test verifies that `samoyed init --dry-run` reports every change init
+# would make without touching the working tree or git config, so users can
+# review init before running it on a shared repository.
+#
+# Tests:
+# 1. Dry run exits successfully and describes each step
+# 2. The git config step shows the exact command
+# 3. Nothing is written to disk and core.hooksPath stays unset
+# 4. --format json prints the plan as a JSON array
+
+# Load test helper functions regardless of current working directory
+integration_script_dir="$(cd "$(dirname "$0")" && pwd)"
+integration_repo_root="$(cd "$integration_script_dir/../.." && pwd)"
+cd "$integration_repo_root"
+. "$integration_repo_root/tests/integration/functions.sh"
+unset integration_script_dir
+unset integration_repo_root
+
+parse_common_args "$@"
+
+# Build Samoyed binary if needed
+build_samoyed
+
+# Set up isolated test environment
+setup
+
+# Test: Human-readable plan
+echo "Testing: samoyed init --dry-run"
+expect 0 "$SAMOYED_BIN init --dry-run"
+output=$("$SAMOYED_BIN" init --dry-run 2>&1)
+
+for expected in \
+    "Would create directory" \
+    "Would write" \
+    "Would run: git config core.hooksPath .samoyed/_"; do
+    if ! echo "$output" | grep -q "$expected"; then
+        error "Expected '$expected' in dry-run output, got: $output"
+    fi
+done
+ok "Dry run described every step"
+
+# Test: Nothing was touched
+echo "Testing: dry run leaves the repository untouched"
+if [ -e ".samoyed" ]; then
+    error "Expected .samoyed not to be created by --dry-run"
+fi
+if git config core.hooksPath >/dev/null 2>&1; then
+    error "Expected core.hooksPath to stay unset after --dry-run"
+fi
+ok "No files written and git config unchanged"
+
+# Test: JSON plan
+echo "Testing: samoyed init --dry-run --format json"
+output=$("$SAMOYED_BIN" init --dry-run --format json 2>&1)
+case "$output" in
+"["*"]") ;;
+*) error "Expected a JSON array, got: $output" ;;
+esac
+if ! echo "$output" | grep -q '"action": "git_config"'; then
+    error "Expected a git_config action in the JSON plan, got: $output"
+fi
+ok "JSON plan printed"
+
+echo
+echo "========================================"
+echo "✅ ALL TESTS PASSED"
+echo "========================================"
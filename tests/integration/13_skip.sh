@@ -0,0 +1,77 @@
+#!/usr/bin/env sh
+# Test: Skipping individual hooks with SAMOYED_SKIP
+#
+# This test verifies that SAMOYED_SKIP disables only the hooks it names, so CI
+# can turn off a single hook (e.g. pre-push) without bypassing every hook with
+# SAMOYED=0.
+#
+# Tests:
+# 1. A listed hook is skipped and the commit succeeds
+# 2. Hooks not in the list still run
+# 3. Matching is exact, so a prefix does not skip a hook
+# 4. SAMOYED=2 prints a notice when a hook is skipped
+# 5. Skipping works when init.sh enables set -u and SAMOYED is unset
+
+# Load test helper functions regardless of current working directory
+integration_script_dir="$(cd "$(dirname "$0")" && pwd)"
+integration_repo_root="$(cd "$integration_script_dir/../.." && pwd)"
+cd "$integration_repo_root"
+. "$integration_repo_root/tests/integration/functions.sh"
+unset integration_script_dir
+unset integration_repo_root
+
+parse_common_args "$@"
+
+# Build Samoyed binary if needed
+build_samoyed
+
+# Set up isolated test environment
+setup
+
+# shellcheck disable=SC2119 # Run init without forwarding script arguments
+init_samoyed
+create_hook "pre-commit" "echo 'pre-commit hook executed' && exit 1"
+
+# Test: A listed hook is skipped
+echo "Testing: SAMOYED_SKIP=pre-commit skips the failing hook"
+echo "skip test" >>test.txt
+git add test.txt
+SAMOYED_SKIP=pre-commit expect 0 "git commit -m 'Skipped pre-commit'"
+ok "Listed hook was skipped"
+
+# Test: Other entries do not affect unlisted hooks
+echo "Testing: SAMOYED_SKIP without pre-commit still runs it"
+echo "unlisted test" >>test.txt
+git add test.txt
+SAMOYED_SKIP="pre-push, commit-msg" expect 1 "git commit -m 'Blocked by pre-commit'"
+ok "Unlisted hook still ran"
+
+# Test: Matching is exact
+echo "Testing: SAMOYED_SKIP matches hook names exactly"
+SAMOYED_SKIP=pre expect 1 "git commit -m 'Blocked by pre-commit'"
+ok "Prefix did not skip the hook"
+
+# Test: Debug mode reports the skip
+echo "Testing: SAMOYED=2 reports skipped hooks"
+output=$(SAMOYED=2 SAMOYED_SKIP=commit-msg,pre-commit git commit -m 'Skipped with notice' 2>&1)
+if ! echo "$output" | grep -q "skipping pre-commit"; then
+    error "Expected skip notice in debug output, got: $output"
+fi
+ok "Skip notice printed in debug mode"
+
+# Test: set -u from init.sh does not break the skip check
+echo "Testing: SAMOYED_SKIP with set -u in init.sh and SAMOYED unset"
+printf 'set -u\n' >.samoyed/init.sh
+echo "strict skip test" >>test.txt
+git add test.txt
+(
+    unset SAMOYED
+    SAMOYED_SKIP=pre-commit expect 0 "git commit -m 'Skipped under set -u'"
+)
+rm .samoyed/init.sh
+ok "Skip works under set -u"
+
+echo
+echo "========================================"
+echo "✅ ALL TESTS PASSED"
+echo "========================================"
@@ -0,0 +1,84 @@
+#!/usr/bin/env sh
+# Test: Init script lookup order
+#
+# This test verifies which initialization script the wrapper sources before a
+# hook runs. Only the first script found is loaded:
+#   1. $SAMOYED_INIT
+#   2. .samoyed/init.sh (repo-local)
+#   3. ${XDG_CONFIG_HOME:-$HOME/.config}/samoyed/init.sh (user-global)
+#
+# Tests:
+# 1. The user-global script is used when nothing else exists
+# 2. A repo-local .samoyed/init.sh takes precedence over the user-global one
+# 3. SAMOYED_INIT takes precedence over both
+# 4. A missing SAMOYED_INIT file is reported and the hook still runs
+
+# Load test helper functions regardless of current working directory
+integration_script_dir="$(cd "$(dirname "$0")" && pwd)"
+integration_repo_root="$(cd "$integration_script_dir/../.." && pwd)"
+cd "$integration_repo_root"
+. "$integration_repo_root/tests/integration/functions.sh"
+unset integration_script_dir
+unset integration_repo_root
+
+parse_common_args "$@"
+
+# Build Samoyed binary if needed
+build_samoyed
+
+# Set up isolated test environment
+setup
+
+# shellcheck disable=SC2119 # Run init without forwarding script arguments
+init_samoyed
+
+# The hook records which init script set INIT_SOURCE
+create_hook "pre-commit" "echo \"init source: \${INIT_SOURCE-none}\""
+
+config_dir="$test_root_dir/config"
+mkdir -p "$config_dir/samoyed"
+echo "export INIT_SOURCE=global" >"$config_dir/samoyed/init.sh"
+
+# Run a commit with the given environment and check which init script was loaded
+# Usage: expect_init_source <source> [VAR=value...]
+expect_init_source() {
+    expect_init_source_name="$1"
+    shift
+    echo "change $expect_init_source_name" >>test.txt
+    git add test.txt
+    output=$(env XDG_CONFIG_HOME="$config_dir" "$@" git commit -m "init source $expect_init_source_name" 2>&1)
+    if ! echo "$output" | grep -q "init source: $expect_init_source_name"; then
+        error "Expected init source '$expect_init_source_name', got: $output"
+    fi
+    unset expect_init_source_name
+}
+
+# Test: User-global script
+echo "Testing: User-global init.sh is loaded"
+expect_init_source global
+ok "User-global init.sh loaded"
+
+# Test: Repo-local script takes precedence
+echo "Testing: Repo-local .samoyed/init.sh takes precedence"
+echo "export INIT_SOURCE=repo" >.samoyed/init.sh
+expect_init_source repo
+ok "Repo-local init.sh loaded instead of user-global"
+
+# Test: SAMOYED_INIT takes precedence over both
+echo "Testing: SAMOYED_INIT takes precedence"
+echo "export INIT_SOURCE=explicit" >"$test_root_dir/explicit.sh"
+expect_init_source explicit SAMOYED_INIT="$test_root_dir/explicit.sh"
+ok "SAMOYED_INIT loaded instead of repo-local and user-global"
+
+# Test: Missing SAMOYED_INIT is reported
+echo "Testing: Missing SAMOYED_INIT is reported"
+expect_init_source none SAMOYED_INIT="$test_root_dir/missing.sh"
+if ! echo "$output" | grep -q "SAMOYED_INIT=.*does not exist"; then
+    error "Expected a missing SAMOYED_INIT notice, got: $output"
+fi
+ok "Missing SAMOYED_INIT reported and hook still ran"
+
+echo
+echo "========================================"
+echo "✅ ALL TESTS PASSED"
+echo "========================================"
@@ -0,0 +1,66 @@
+#!/usr/bin/env sh
+# Test: Git-provided stdin reaches the user hook
+#
+# This test verifies that hooks which receive data on stdin, such as pre-push,
+# see that data unchanged. Git writes one line per pushed ref to pre-push's
+# stdin, and the wrapper must not consume or replace it.
+#
+# Tests:
+# 1. pre-push receives the local and remote ref lines from git push
+# 2. pre-push receives the remote name and URL as arguments
+# 3. A pre-push hook that rejects based on stdin blocks the push
+
+# Load test helper functions regardless of current working directory
+integration_script_dir="$(cd "$(dirname "$0")" && pwd)"
+integration_repo_root="$(cd "$integration_script_dir/../.." && pwd)"
+cd "$integration_repo_root"
+. "$integration_repo_root/tests/integration/functions.sh"
+unset integration_script_dir
+unset integration_repo_root
+
+parse_common_args "$@"
+
+# Build Samoyed binary if needed
+build_samoyed
+
+# Set up isolated test environment
+setup
+
+# shellcheck disable=SC2119 # Run init without forwarding script arguments
+init_samoyed
+
+# Push to a local bare repository
+git init --quiet --bare "$test_root_dir/remote.git"
+git remote add origin "$test_root_dir/remote.git"
+branch=$(git symbolic-ref --short HEAD)
+
+# Test: stdin and arguments reach the hook
+echo "Testing: pre-push receives stdin and arguments"
+create_hook "pre-push" "echo \"\$1 \$2\" >\"$test_root_dir/args.txt\"; cat >\"$test_root_dir/stdin.txt\""
+expect 0 "git push --quiet origin $branch"
+
+expect_file_exists "$test_root_dir/stdin.txt"
+head_sha=$(git rev-parse HEAD)
+if ! grep -q "^refs/heads/$branch $head_sha refs/heads/$branch " "$test_root_dir/stdin.txt"; then
+    error "Expected ref line on stdin, got: $(cat "$test_root_dir/stdin.txt")"
+fi
+ok "pre-push received the ref lines on stdin"
+
+if [ "$(cat "$test_root_dir/args.txt")" != "origin $test_root_dir/remote.git" ]; then
+    error "Expected remote name and URL as arguments, got: $(cat "$test_root_dir/args.txt")"
+fi
+ok "pre-push received the remote name and URL"
+
+# Test: A hook can reject a push based on stdin
+echo "Testing: pre-push rejects based on stdin"
+create_hook "pre-push" "while read -r local_ref _; do case \"\$local_ref\" in refs/heads/*) echo \"blocked \$local_ref\"; exit 1 ;; esac; done"
+echo "stdin test" >>test.txt
+git add test.txt
+git commit --quiet -m "Push with stdin check"
+expect 1 "git push --quiet origin $branch"
+ok "pre-push blocked the push based on stdin"
+
+echo
+echo "========================================"
+echo "✅ ALL TESTS PASSED"
+echo "========================================"
@@ -0,0 +1,61 @@
+#!/usr/bin/env sh
+# Test: Choosing the hook interpreter with --shell
+#
+# This test verifies that `samoyed init --shell bash` makes Git run both the
+# generated hook scripts and the user's hooks with bash, so hooks can rely on
+# bash features even where sh is a minimal shell such as dash.
+#
+# Tests:
+# 1. Generated hook scripts use a bash shebang
+# 2. A user hook using bash-only syntax runs under bash
+# 3. An invalid --shell value is rejected
+
+# Load test helper functions regardless of current working directory
+integration_script_dir="$(cd "$(dirname "$0")" && pwd)"
+integration_repo_root="$(cd "$integration_script_dir/../.." && pwd)"
+cd "$integration_repo_root"
+. "$integration_repo_root/tests/integration/functions.sh"
+unset integration_script_dir
+unset integration_repo_root
+
+parse_common_args "$@"
+
+# Build Samoyed binary if needed
+build_samoyed
+
+# Set up isolated test environment
+setup
+
+if ! command -v bash >/dev/null 2>&1; then
+    echo "bash not found; skipping --shell tests"
+    exit 0
+fi
+
+# Test: Generated scripts use bash
+echo "Testing: samoyed init --shell bash"
+expect 0 "$SAMOYED_BIN init --shell bash"
+if [ "$(head -n 1 .samoyed/_/pre-commit)" != "#!/usr/bin/env bash" ]; then
+    error "Expected a bash shebang, got: $(head -n 1 .samoyed/_/pre-commit)"
+fi
+ok "Hook scripts use bash"
+
+# Test: User hook runs under bash
+echo "Testing: User hook with bash-only syntax"
+create_hook "pre-commit" "if [[ -n \"\${BASH_VERSION-}\" ]]; then echo 'running in bash'; else exit 1; fi"
+echo "bash test" >>test.txt
+git add test.txt
+output=$(git commit -m "Commit with bash hook" 2>&1) || error "Commit failed: $output"
+if ! echo "$output" | grep -q "running in bash"; then
+    error "Expected the hook to run in bash, got: $output"
+fi
+ok "User hook ran under bash"
+
+# Test: Invalid shell
+echo "Testing: Invalid --shell value"
+expect 1 "$SAMOYED_BIN init --shell 'bash -x' 2>/dev/null"
+ok "Invalid shell rejected"
+
+echo
+echo "========================================"
+echo "✅ ALL TESTS PASSED"
+echo "========================================"
@@ -0,0 +1,68 @@
+#!/usr/bin/env sh
+# Test: Installing into another repository with --cwd
+#
+# This test verifies that `samoyed init --cwd <path>` installs into the
+# repository at <path> without changing directory first, so provisioning
+# scripts can set up many clones from one place.
+#
+# Tests:
+# 1. init --cwd installs into the other repository
+# 2. The current repository is left untouched
+# 3. Relative samoyed dirnames resolve inside the other repository
+# 4. A missing --cwd directory fails with a clear error
+
+# Load test helper functions regardless of current working directory
+integration_script_dir="$(cd "$(dirname "$0")" && pwd)"
+integration_repo_root="$(cd "$integration_script_dir/../.." && pwd)"
+cd "$integration_repo_root"
+. "$integration_repo_root/tests/integration/functions.sh"
+unset integration_script_dir
+unset integration_repo_root
+
+parse_common_args "$@"
+
+# Build Samoyed binary if needed
+build_samoyed
+
+# Set up isolated test environment
+setup
+
+other_repo="$test_root_dir/other"
+git init --quiet "$other_repo"
+
+# Test: Install into the other repository
+echo "Testing: samoyed init --cwd <path>"
+expect 0 "$SAMOYED_BIN init --cwd '$other_repo'"
+expect_dir_exists "$other_repo/.samoyed/_"
+expect_file_exists "$other_repo/.samoyed/_/pre-commit"
+if [ "$(git -C "$other_repo" config core.hooksPath)" != ".samoyed/_" ]; then
+    error "Expected core.hooksPath .samoyed/_ in $other_repo"
+fi
+ok "Installed into the --cwd repository"
+
+# Test: The current repository is untouched
+echo "Testing: Current repository is left alone"
+if [ -d ".samoyed" ]; then
+    error "init --cwd created .samoyed in the current directory"
+fi
+expect_hooks_path_to_be ""
+ok "Current repository untouched"
+
+# Test: Custom dirname resolves inside the --cwd repository
+echo "Testing: samoyed init --cwd <path> my-hooks"
+expect 0 "$SAMOYED_BIN init --cwd '$other_repo' my-hooks"
+expect_dir_exists "$other_repo/my-hooks/_"
+ok "Custom dirname resolved inside the --cwd repository"
+
+# Test: Missing directory
+echo "Testing: --cwd with a missing directory"
+output=$("$SAMOYED_BIN" init --cwd "$test_root_dir/missing" 2>&1) && error "Expected init to fail"
+if ! echo "$output" | grep -q "Failed to change to directory"; then
+    error "Expected a change-directory error, got: $output"
+fi
+ok "Missing --cwd directory reported"
+
+echo
+echo "========================================"
+echo "✅ ALL TESTS PASSED"
+echo "========================================"
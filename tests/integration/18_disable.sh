@@ -0,0 +1,69 @@
+#!/usr/bin/env sh
+# Test: Turning hooks off and on with samoyed disable / enable
+#
+# This test verifies that `samoyed disable` leaves a marker that makes every
+# hook exit early with a notice, that the marker is git-ignored, and that
+# `samoyed enable` brings the hooks back.
+#
+# Tests:
+# 1. disable skips a failing hook and prints a notice on stderr
+# 2. The marker is not picked up by `git status`
+# 3. enable runs the hook again
+# 4. disable fails before samoyed init
+
+# Load test helper functions regardless of current working directory
+integration_script_dir="$(cd "$(dirname "$0")" && pwd)"
+integration_repo_root="$(cd "$integration_script_dir/../.." && pwd)"
+cd "$integration_repo_root"
+. "$integration_repo_root/tests/integration/functions.sh"
+unset integration_script_dir
+unset integration_repo_root
+
+parse_common_args "$@"
+
+# Build Samoyed binary if needed
+build_samoyed
+
+# Set up isolated test environment
+setup
+
+# Test: disable before init is rejected
+echo "Testing: samoyed disable before init"
+expect 1 "$SAMOYED_BIN disable"
+ok "disable refused without an installation"
+
+# shellcheck disable=SC2119 # Run init without forwarding script arguments
+init_samoyed
+create_hook "pre-commit" "echo 'pre-commit hook executed' && exit 1"
+git add .samoyed/pre-commit
+
+# Test: disable skips hooks with a notice
+echo "Testing: samoyed disable skips the failing hook"
+expect 0 "$SAMOYED_BIN disable"
+echo "disable test" >>test.txt
+git add test.txt
+output=$(git commit -m 'Committed while disabled' 2>&1) || error "Expected commit to succeed, got: $output"
+if ! echo "$output" | grep -q "pre-commit skipped: hooks are disabled"; then
+    error "Expected disabled notice, got: $output"
+fi
+ok "Hook was skipped with a notice"
+
+# Test: The marker never shows up as a change
+echo "Testing: the disable marker is git-ignored"
+if git status --porcelain --ignored=no | grep -q "disabled"; then
+    error "Disable marker shows up in git status"
+fi
+ok "Marker is ignored by git"
+
+# Test: enable brings the hook back
+echo "Testing: samoyed enable runs hooks again"
+expect 0 "$SAMOYED_BIN enable"
+echo "enable test" >>test.txt
+git add test.txt
+expect 1 "git commit -m 'Blocked by pre-commit'"
+ok "Hook ran again after enable"
+
+echo
+echo "========================================"
+echo "✅ ALL TESTS PASSED"
+echo "========================================"
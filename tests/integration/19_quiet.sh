@@ -0,0 +1,61 @@
+#!/usr/bin/env sh
+# Test: --quiet keeps stdout empty on success but still reports errors
+#
+# This test verifies that scripted installs can rely on `--quiet` printing
+# nothing when a command succeeds, while failures still reach stderr and the
+# exit codes stay the same.
+#
+# Tests:
+# 1. Successful commands print nothing with --quiet
+# 2. The SAMOYED=0 bypass notice and the --summary output respect --quiet
+# 3. Errors are still printed with --quiet and exit non-zero
+
+# Load test helper functions regardless of current working directory
+integration_script_dir="$(cd "$(dirname "$0")" && pwd)"
+integration_repo_root="$(cd "$integration_script_dir/../.." && pwd)"
+cd "$integration_repo_root"
+. "$integration_repo_root/tests/integration/functions.sh"
+unset integration_script_dir
+unset integration_repo_root
+
+parse_common_args "$@"
+
+# Build Samoyed binary if needed
+build_samoyed
+
+# Set up isolated test environment
+setup
+
+# Fail unless the given command succeeds without printing anything
+expect_silent() {
+    output=$(eval "$1" 2>&1) || error "Expected '$1' to succeed, got: $output"
+    if [ -n "$output" ]; then
+        error "Expected no output from '$1', got: $output"
+    fi
+}
+
+# Test: Successful commands are silent
+echo "Testing: successful commands print nothing with --quiet"
+expect_silent "SAMOYED=0 $SAMOYED_BIN --quiet init"
+expect_silent "$SAMOYED_BIN --quiet init --summary"
+expect_silent "$SAMOYED_BIN --quiet validate-dirname .samoyed"
+expect_silent "$SAMOYED_BIN --quiet disable"
+expect_silent "$SAMOYED_BIN --quiet enable"
+expect_silent "$SAMOYED_BIN --quiet reinstall"
+expect_silent "$SAMOYED_BIN --quiet migrate"
+expect_silent "$SAMOYED_BIN --quiet uninstall"
+expect_silent "$SAMOYED_BIN --quiet uninstall"
+ok "No output on success"
+
+# Test: Errors still print
+echo "Testing: errors are reported with --quiet"
+output=$("$SAMOYED_BIN" --quiet validate-dirname ../outside 2>&1) && error "Expected validate-dirname to fail"
+if ! echo "$output" | grep -q "outside the git repository"; then
+    error "Expected an error message, got: $output"
+fi
+ok "Errors still printed"
+
+echo
+echo "========================================"
+echo "✅ ALL TESTS PASSED"
+echo "========================================"
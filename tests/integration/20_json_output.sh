@@ -0,0 +1,72 @@
+#!/usr/bin/env sh
+# Test: init --format json writes nothing but the JSON document to stdout
+#
+# This test verifies that editor integrations can parse the stdout of
+# `samoyed init --format json` on its own, even when init has notices or
+# progress messages to report along the way.
+#
+# Tests:
+# 1. Taking over a foreign core.hooksPath and resetting the sample pre-commit
+# 2. Running init inside a linked worktree
+# 3. Replacing a stale wrapper script
+
+# Load test helper functions regardless of current working directory
+integration_script_dir="$(cd "$(dirname "$0")" && pwd)"
+integration_repo_root="$(cd "$integration_script_dir/../.." && pwd)"
+cd "$integration_repo_root"
+. "$integration_repo_root/tests/integration/functions.sh"
+unset integration_script_dir
+unset integration_repo_root
+
+parse_common_args "$@"
+
+# Build Samoyed binary if needed
+build_samoyed
+
+# Set up isolated test environment
+setup
+
+# Fail unless the given command succeeds and its stdout alone is one JSON object
+expect_json_stdout() {
+    stdout=$(eval "$1" 2>/dev/null) || error "Expected '$1' to succeed"
+    if command -v python3 >/dev/null 2>&1; then
+        if ! printf '%s' "$stdout" | python3 -c 'import json, sys; json.load(sys.stdin)' 2>/dev/null; then
+            error "Expected JSON on stdout from '$1', got: $stdout"
+        fi
+    else
+        first=$(printf '%s\n' "$stdout" | head -n 1)
+        last=$(printf '%s\n' "$stdout" | tail -n 1)
+        if [ "$first" != "{" ] || [ "$last" != "}" ]; then
+            error "Expected JSON on stdout from '$1', got: $stdout"
+        fi
+    fi
+}
+
+# Test: --force and --reset-samples
+echo "Testing: taking over core.hooksPath and resetting the sample"
+"$SAMOYED_BIN" init >/dev/null
+echo "custom" >>.samoyed/pre-commit
+git config core.hooksPath .githooks
+expect_json_stdout "$SAMOYED_BIN init --force --reset-samples --format json"
+expect_hooks_path_to_be ".samoyed/_"
+ok "Force and sample reset keep stdout parseable"
+
+# Test: linked worktree
+echo "Testing: init in a linked worktree"
+git worktree add --quiet ../linked
+cd ../linked
+expect_json_stdout "$SAMOYED_BIN init --format json"
+cd "$test_dir"
+ok "Linked worktree notice stays off stdout"
+
+# Test: stale wrapper
+echo "Testing: replacing a stale wrapper script"
+sed 's/^# samoyed-wrapper-version: .*/# samoyed-wrapper-version: 0/' .samoyed/_/samoyed >wrapper.tmp
+chmod +x wrapper.tmp
+mv wrapper.tmp .samoyed/_/samoyed
+expect_json_stdout "$SAMOYED_BIN --verbose init --format json"
+ok "Stale wrapper update stays off stdout"
+
+echo
+echo "========================================"
+echo "✅ ALL TESTS PASSED"